@@ -1,10 +1,20 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use super::SystemClock;
 use super::{
     client::{Client, RedirectUriInvalidError},
-    Prompt, Scope,
+    form::{self, FormWriter},
+    id::GuildId,
+    session_store::AuthorizationSession,
+    Clock, Prompt, Scope,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::Write;
-use twilight_model::{guild::Permissions, id::GuildId};
+use std::{
+    borrow::Borrow,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+#[cfg(feature = "model")]
+use twilight_model::guild::Permissions;
 use url::Url;
 
 /// Type of response to give after authorization approval.
@@ -43,16 +53,100 @@ impl ResponseType {
     }
 }
 
+/// Method used to derive a PKCE code challenge from its code verifier.
+///
+/// Read about [RFC 7636 § 4.2] for how the challenge is derived.
+///
+/// [RFC 7636 § 4.2]: https://tools.ietf.org/html/rfc7636#section-4.2
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub enum CodeChallengeMethod {
+    /// The challenge is the code verifier itself.
+    #[serde(rename = "plain")]
+    Plain,
+    /// The challenge is the unpadded base64url encoding of the SHA-256 hash
+    /// of the code verifier.
+    S256,
+}
+
+impl CodeChallengeMethod {
+    /// Name of the code challenge method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::authorization_url::CodeChallengeMethod;
+    ///
+    /// assert_eq!("plain", CodeChallengeMethod::Plain.name());
+    /// assert_eq!("S256", CodeChallengeMethod::S256.name());
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::S256 => "S256",
+        }
+    }
+}
+
+/// Building an authorization URL failed.
+///
+/// This is returned from [`AuthorizationUrlBuilder::build_checked`].
+///
+/// [`AuthorizationUrlBuilder::build_checked`]: struct.AuthorizationUrlBuilder.html#method.build_checked
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The built URL is longer than the configured maximum length.
+    UrlTooLong {
+        /// Length of the built URL.
+        length: usize,
+        /// Configured maximum length.
+        limit: usize,
+    },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::UrlTooLong { length, limit } => f.write_fmt(format_args!(
+                "the authorization url is {length} characters long, but the limit is {limit}",
+            )),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Builder for the URL to send a user to for authorizing an application.
+///
+/// Optional parameters, such as the [prompt] and [state], are only in the URL
+/// when they've been set. Setting one to a value Discord would default to
+/// still includes it, so URLs only differ when their options do.
+///
+/// This is created via [`Client::authorization_url`].
+///
+/// [`Client::authorization_url`]: ../client/struct.Client.html#method.authorization_url
+/// [prompt]: #method.prompt
+/// [state]: #method.state
 #[derive(Clone, Debug)]
 pub struct AuthorizationUrlBuilder<'a> {
     client: &'a Client,
+    code_challenge: Option<(&'a str, CodeChallengeMethod)>,
+    max_url_length: usize,
     prompt: Option<Prompt>,
     redirect_uri: &'a Url,
-    scopes: Option<&'a [Scope]>,
+    scopes: Vec<Scope>,
     state: Option<&'a str>,
 }
 
 impl<'a> AuthorizationUrlBuilder<'a> {
+    /// Default maximum length of URLs built by [`build_checked`].
+    ///
+    /// Some browsers, proxies, and CDNs refuse URLs longer than this.
+    ///
+    /// [`build_checked`]: #method.build_checked
+    pub const DEFAULT_MAX_URL_LENGTH: usize = 2048;
+
     pub(crate) fn new(
         client: &'a Client,
         redirect_uri: &'a str,
@@ -61,9 +155,11 @@ impl<'a> AuthorizationUrlBuilder<'a> {
 
         Ok(Self {
             client,
+            code_challenge: None,
+            max_url_length: Self::DEFAULT_MAX_URL_LENGTH,
             prompt: None,
             redirect_uri,
-            scopes: None,
+            scopes: client.default_scopes().to_vec(),
             state: None,
         })
     }
@@ -72,6 +168,40 @@ impl<'a> AuthorizationUrlBuilder<'a> {
         self.build_with_response_type(ResponseType::Code)
     }
 
+    /// Append the URL returned by [`build`] to a buffer.
+    ///
+    /// Existing contents of the buffer are left untouched. This allows
+    /// reusing one buffer when building many URLs.
+    ///
+    /// [`build`]: #method.build
+    pub fn write_url(&self, buf: &mut String) {
+        self.write_with_response_type(buf, ResponseType::Code);
+    }
+
+    /// Build the authorization URL, checking that it isn't too long.
+    ///
+    /// Refer to [`max_url_length`] for configuring the maximum length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::UrlTooLong`] if the built URL is longer than the
+    /// configured maximum length.
+    ///
+    /// [`BuildError::UrlTooLong`]: enum.BuildError.html#variant.UrlTooLong
+    /// [`max_url_length`]: #method.max_url_length
+    pub fn build_checked(&self) -> Result<String, BuildError> {
+        let url = self.build();
+
+        if url.len() > self.max_url_length {
+            return Err(BuildError::UrlTooLong {
+                length: url.len(),
+                limit: self.max_url_length,
+            });
+        }
+
+        Ok(url)
+    }
+
     /// Build the authorization URL into an implicit grant URL.
     ///
     /// Contrasted from [`build`], this will contain URI fragments after a hash
@@ -93,47 +223,121 @@ impl<'a> AuthorizationUrlBuilder<'a> {
     /// [`WebhookIncoming`]: ../enum.Scope.html#variant.WebhookIncoming
     /// [`webhook_token_exchange`]: ../request/webhook_token_exchange/index.html
     pub fn webhook(&mut self) -> String {
-        self.scopes(&[Scope::WebhookIncoming]);
+        self.scopes([Scope::WebhookIncoming]);
 
         self.build_with_response_type(ResponseType::Code)
     }
 
     fn build_with_response_type(&self, response_type: ResponseType) -> String {
-        let mut url = Client::BASE_URI.to_owned();
-        url.push('?');
-        url.push_str("response_type=");
-        url.push_str(response_type.name());
-        url.push_str("&client_id=");
-        write!(url, "{}", self.client.client_id().0).expect("client id write can't error");
+        let mut buf = String::new();
+        self.write_with_response_type(&mut buf, response_type);
 
-        if let Some(scopes) = self.scopes.as_ref() {
-            url.push_str("&scope=");
+        buf
+    }
 
-            let scope_count = scopes.len().saturating_sub(1);
+    fn write_with_response_type(&self, buf: &mut String, response_type: ResponseType) {
+        form::append(buf, |url| {
+            url.push_raw(Client::BASE_URI);
+            url.push_raw("?response_type=");
+            url.push_raw(response_type.name());
+            url.push_raw("&client_id=");
+            url.push_number(self.client.client_id().0);
 
-            for (idx, scope) in scopes.iter().enumerate() {
-                url.push_str(scope.name());
+            if !self.scopes.is_empty() {
+                url.push_raw("&scope=");
+                write_scopes(url, &self.scopes);
+            }
 
-                if idx < scope_count {
-                    url.push_str("%20");
-                }
+            if let Some(state) = self.state {
+                url.push_raw("&state=");
+                url.push_encoded(state);
             }
-        }
 
-        if let Some(state) = self.state.as_ref() {
-            url.push_str("&state=");
-            url.push_str(state);
-        }
+            url.push_raw("&redirect_uri=");
+            url.push_encoded(self.client.redirect_uri_text(self.redirect_uri));
 
-        url.push_str("&redirect_uri=");
-        url.push_str(&urlencoding::encode(self.redirect_uri.as_ref()));
+            if let Some(prompt) = self.prompt.as_ref().map(Prompt::name) {
+                url.push_raw("&prompt=");
+                url.push_raw(prompt);
+            }
 
-        if let Some(prompt) = self.prompt.as_ref().map(Prompt::name) {
-            url.push_str("&prompt=");
-            url.push_str(prompt);
-        }
+            if let Some((challenge, method)) = self.code_challenge {
+                url.push_raw("&code_challenge=");
+                url.push_encoded(challenge);
+                url.push_raw("&code_challenge_method=");
+                url.push_raw(method.name());
+            }
+        });
+    }
+
+    /// Set the PKCE code challenge for the authorization request, and the
+    /// method it was derived from the code verifier with.
+    ///
+    /// The code verifier must then be sent when exchanging the code. With
+    /// [`CodeChallengeMethod::Plain`] the challenge is the code verifier, so
+    /// sessions created via [`session_with`] record it. With
+    /// [`CodeChallengeMethod::S256`] set the verifier on the session via
+    /// [`AuthorizationSession::code_verifier`].
+    ///
+    /// Read about [RFC 7636 § 4.3] for more information.
+    ///
+    /// [`AuthorizationSession::code_verifier`]: ../session_store/struct.AuthorizationSession.html#method.code_verifier
+    /// [`CodeChallengeMethod::Plain`]: enum.CodeChallengeMethod.html#variant.Plain
+    /// [`CodeChallengeMethod::S256`]: enum.CodeChallengeMethod.html#variant.S256
+    /// [`session_with`]: #method.session_with
+    /// [RFC 7636 § 4.3]: https://tools.ietf.org/html/rfc7636#section-4.3
+    pub fn code_challenge(
+        &mut self,
+        code_challenge: &'a str,
+        method: CodeChallengeMethod,
+    ) -> &mut Self {
+        self.code_challenge.replace((code_challenge, method));
+
+        self
+    }
 
-        url
+    /// Set the PKCE code challenge for the authorization request, and the
+    /// method it was derived from the code verifier with.
+    ///
+    /// This is the by-value form of [`code_challenge`], for building in one
+    /// expression.
+    ///
+    /// [`code_challenge`]: #method.code_challenge
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_code_challenge(
+        mut self,
+        code_challenge: &'a str,
+        method: CodeChallengeMethod,
+    ) -> Self {
+        self.code_challenge(code_challenge, method);
+
+        self
+    }
+
+    /// Set the maximum length of URLs built by [`build_checked`].
+    ///
+    /// Defaults to [`DEFAULT_MAX_URL_LENGTH`].
+    ///
+    /// [`DEFAULT_MAX_URL_LENGTH`]: #associatedconstant.DEFAULT_MAX_URL_LENGTH
+    /// [`build_checked`]: #method.build_checked
+    pub fn max_url_length(&mut self, limit: usize) -> &mut Self {
+        self.max_url_length = limit;
+
+        self
+    }
+
+    /// Set the maximum length of URLs built by [`build_checked`].
+    ///
+    /// This is the by-value form of [`max_url_length`], for building in one
+    /// expression.
+    ///
+    /// [`build_checked`]: #method.build_checked
+    /// [`max_url_length`]: #method.max_url_length
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_max_url_length(mut self, limit: usize) -> Self {
+        self.max_url_length(limit);
+
+        self
     }
 
     /// Set how to prompt the user for authorization.
@@ -153,8 +357,51 @@ impl<'a> AuthorizationUrlBuilder<'a> {
         self
     }
 
+    /// Set how to prompt the user for authorization.
+    ///
+    /// This is the by-value form of [`prompt`], for building in one
+    /// expression.
+    ///
+    /// [`prompt`]: #method.prompt
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_prompt(mut self, prompt: Prompt) -> Self {
+        self.prompt(prompt);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the authorization request.
+    ///
+    /// This appends to the client's [default scopes] or the scopes previously
+    /// set via [`scopes`].
+    ///
+    /// [`scopes`]: #method.scopes
+    /// [default scopes]: ../client/struct.Client.html#method.with_default_scopes
+    pub fn add_scope(&mut self, scope: Scope) -> &mut Self {
+        self.scopes.push(scope);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the authorization request.
+    ///
+    /// This is the by-value form of [`add_scope`], for building in one
+    /// expression.
+    ///
+    /// [`add_scope`]: #method.add_scope
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.add_scope(scope);
+
+        self
+    }
+
     /// Set the scopes for the authorization request.
     ///
+    /// This overrides the client's [default scopes]. Scopes or references to
+    /// scopes can be passed from any iterator, such as an array, a slice, or
+    /// an iterator chain; they're copied into a vector owned by the builder.
+    ///
     /// If you set the [`WebhookIncoming`] scope then a webhook will be
     /// returned on the [`AccessTokenExchangeResponse`].
     ///
@@ -165,9 +412,23 @@ impl<'a> AuthorizationUrlBuilder<'a> {
     /// [`AccessTokenExchangeResponse`]: ../request/access_token_exchange/struct.AccessTokenExchangeResponse.html
     /// [`WebhookIncoming`]: ../enum.Scope.html#variant.WebhookIncoming
     /// [RFC 6749 § 3.3]: https://tools.ietf.org/html/rfc6749#section-3.3
+    /// [default scopes]: ../client/struct.Client.html#method.with_default_scopes
     /// [scope documentation]: https://discord.com/developers/docs/topics/oauth2#shared-resources-oauth2-scopes
-    pub fn scopes(&mut self, scopes: &'a [Scope]) -> &mut Self {
-        self.scopes.replace(scopes);
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes = scopes.into_iter().map(|scope| *scope.borrow()).collect();
+
+        self
+    }
+
+    /// Set the scopes for the authorization request.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
 
         self
     }
@@ -186,16 +447,80 @@ impl<'a> AuthorizationUrlBuilder<'a> {
 
         self
     }
+
+    /// Set the state for the authorization request.
+    ///
+    /// This is the by-value form of [`state`], for building in one
+    /// expression.
+    ///
+    /// [`state`]: #method.state
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_state(mut self, state: &'a str) -> Self {
+        self.state(state);
+
+        self
+    }
+
+    /// Create a session to remember until the redirect is handled, with the
+    /// state and redirect URI of the URL.
+    ///
+    /// Returns `None` if no state is set, since sessions are looked up by
+    /// their state. Refer to the [`session_store`] module for an example.
+    ///
+    /// [`session_store`]: ../session_store/index.html
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn session(&self) -> Option<AuthorizationSession> {
+        self.session_with(&SystemClock)
+    }
+
+    /// Create a session to remember until the redirect is handled, created
+    /// at the clock's current time.
+    ///
+    /// The code verifier is recorded if the [code challenge] uses the
+    /// [`CodeChallengeMethod::Plain`] method.
+    ///
+    /// Returns `None` if no state is set.
+    ///
+    /// [`CodeChallengeMethod::Plain`]: enum.CodeChallengeMethod.html#variant.Plain
+    /// [code challenge]: #method.code_challenge
+    pub fn session_with(&self, clock: &impl Clock) -> Option<AuthorizationSession> {
+        let state = self.state?;
+        let mut session = AuthorizationSession::new_with(
+            state,
+            self.client.redirect_uri_text(self.redirect_uri),
+            clock,
+        );
+
+        if let Some((code_verifier, CodeChallengeMethod::Plain)) = self.code_challenge {
+            session.code_verifier(code_verifier);
+        }
+
+        Some(session)
+    }
 }
 
+/// Builder for the URL to send a user to for adding a bot to a guild.
+///
+/// Optional parameters, such as the [guild ID] and [permissions], are only in
+/// the URL when they've been set. Setting one to a value Discord would
+/// default to, such as disabling guild selection with `false`, still
+/// includes it. Scopes are omitted when set to an empty list.
+///
+/// This is created via [`Client::bot_authorization_url`].
+///
+/// [`Client::bot_authorization_url`]: ../client/struct.Client.html#method.bot_authorization_url
+/// [guild ID]: #method.guild_id
+/// [permissions]: #method.raw_permissions
 #[derive(Clone, Debug)]
 pub struct BotAuthorizationUrlBuilder<'a> {
     client: &'a Client,
     disable_guild_select: Option<bool>,
     guild_id: Option<GuildId>,
-    permissions: Option<Permissions>,
+    permissions: Option<u64>,
     redirect_uri: Option<&'a Url>,
-    scopes: &'a [Scope],
+    response_type: Option<ResponseType>,
+    scopes: Vec<Scope>,
+    state: Option<&'a str>,
 }
 
 impl<'a> BotAuthorizationUrlBuilder<'a> {
@@ -206,53 +531,81 @@ impl<'a> BotAuthorizationUrlBuilder<'a> {
             guild_id: None,
             permissions: None,
             redirect_uri: None,
-            scopes: &[Scope::Bot],
+            response_type: None,
+            scopes: vec![Scope::Bot],
+            state: None,
         }
     }
 
+    /// Request an authorization code, redirecting the user to an already
+    /// validated redirect URI after they add the bot.
+    #[cfg(feature = "model")]
+    pub(crate) fn code_grant(&mut self, redirect_uri: &'a Url) -> &mut Self {
+        self.redirect_uri.replace(redirect_uri);
+        self.response_type.replace(ResponseType::Code);
+
+        self
+    }
+
     /// Build a bot authorization URL.
     pub fn build(&self) -> String {
-        let mut url = Client::BASE_URI.to_owned();
-        url.push_str("?client_id=");
-        let _ = write!(url, "{}", self.client.client_id().0);
-
-        if let Some(disable_guild_select) = self.disable_guild_select {
-            url.push_str("&disable_guild_select=");
-            url.push_str(if disable_guild_select {
-                "true"
-            } else {
-                "false"
-            });
-        }
+        let mut buf = String::new();
+        self.write_url(&mut buf);
 
-        if let Some(guild_id) = self.guild_id {
-            url.push_str("&guild_id=");
-            let _ = write!(url, "{}", guild_id.0);
-        }
+        buf
+    }
 
-        if let Some(permissions) = self.permissions {
-            url.push_str("&permissions=");
-            let _ = write!(url, "{}", permissions.bits());
-        }
+    /// Append the URL returned by [`build`] to a buffer.
+    ///
+    /// Existing contents of the buffer are left untouched. This allows
+    /// reusing one buffer when building many URLs.
+    ///
+    /// [`build`]: #method.build
+    pub fn write_url(&self, buf: &mut String) {
+        form::append(buf, |url| {
+            url.push_raw(Client::BASE_URI);
+            url.push_raw("?client_id=");
+            url.push_number(self.client.client_id().0);
 
-        if let Some(redirect_uri) = self.redirect_uri.as_ref() {
-            url.push_str("&redirect_uri=");
-            url.push_str(&urlencoding::encode(redirect_uri.as_ref()));
-        }
+            if let Some(disable_guild_select) = self.disable_guild_select {
+                url.push_raw("&disable_guild_select=");
+                url.push_raw(if disable_guild_select {
+                    "true"
+                } else {
+                    "false"
+                });
+            }
 
-        url.push_str("&scope=");
+            if let Some(guild_id) = self.guild_id {
+                url.push_raw("&guild_id=");
+                url.push_number(guild_id.0);
+            }
 
-        let scope_count = self.scopes.len().saturating_sub(1);
+            if let Some(permissions) = self.permissions {
+                url.push_raw("&permissions=");
+                url.push_number(permissions);
+            }
 
-        for (idx, scope) in self.scopes.iter().enumerate() {
-            url.push_str(scope.name());
+            if let Some(redirect_uri) = self.redirect_uri.as_ref() {
+                url.push_raw("&redirect_uri=");
+                url.push_encoded(self.client.redirect_uri_text(redirect_uri));
+            }
 
-            if idx < scope_count {
-                url.push_str("%20");
+            if let Some(response_type) = self.response_type {
+                url.push_raw("&response_type=");
+                url.push_raw(response_type.name());
             }
-        }
 
-        url
+            if !self.scopes.is_empty() {
+                url.push_raw("&scope=");
+                write_scopes(url, &self.scopes);
+            }
+
+            if let Some(state) = self.state {
+                url.push_raw("&state=");
+                url.push_encoded(state);
+            }
+        });
     }
 
     /// Set whether to disable selection of what guild to authorize.
@@ -266,6 +619,19 @@ impl<'a> BotAuthorizationUrlBuilder<'a> {
         self
     }
 
+    /// Set whether to disable selection of what guild to authorize.
+    ///
+    /// This is the by-value form of [`disable_guild_select`], for building in one
+    /// expression.
+    ///
+    /// [`disable_guild_select`]: #method.disable_guild_select
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_disable_guild_select(mut self, disable_guild_select: bool) -> Self {
+        self.disable_guild_select(disable_guild_select);
+
+        self
+    }
+
     /// Set the ID of the guild to recommend the user to authorize the bot to.
     ///
     /// You must set this if [`disable_guild_select`] is set to `true`.
@@ -277,14 +643,91 @@ impl<'a> BotAuthorizationUrlBuilder<'a> {
         self
     }
 
+    /// Set the ID of the guild to recommend the user to authorize the bot to.
+    ///
+    /// This is the by-value form of [`guild_id`], for building in one
+    /// expression.
+    ///
+    /// [`guild_id`]: #method.guild_id
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_guild_id(mut self, guild_id: GuildId) -> Self {
+        self.guild_id(guild_id);
+
+        self
+    }
+
     /// Set the permissions to request the bot be given when authorized to a
     /// guild.
+    ///
+    /// Use [`raw_permissions`] when the `model` feature is disabled.
+    ///
+    /// # Examples
+    ///
+    /// Request the "Send Messages" permission:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{guild::Permissions, id::ApplicationId};
+    /// use twilight_oauth2::Client;
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+    /// let url = client
+    ///     .bot_authorization_url()
+    ///     .permissions(Permissions::SEND_MESSAGES)
+    ///     .build();
+    ///
+    /// println!("bot authorization url: {}", url);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`raw_permissions`]: #method.raw_permissions
+    #[cfg(feature = "model")]
     pub fn permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.raw_permissions(permissions.bits())
+    }
+
+    /// Set the permissions to request the bot be given when authorized to a
+    /// guild.
+    ///
+    /// This is the by-value form of [`permissions`], for building in one
+    /// expression.
+    ///
+    /// [`permissions`]: #method.permissions
+    #[cfg(feature = "model")]
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions(permissions);
+
+        self
+    }
+
+    /// Set the permissions to request the bot be given when authorized to a
+    /// guild, as a raw bitfield.
+    ///
+    /// Refer to Discord's [permissions documentation] for the values of each
+    /// permission.
+    ///
+    /// [permissions documentation]: https://discord.com/developers/docs/topics/permissions
+    pub fn raw_permissions(&mut self, permissions: u64) -> &mut Self {
         self.permissions.replace(permissions);
 
         self
     }
 
+    /// Set the permissions to request the bot be given when authorized to a
+    /// guild, as a raw bitfield.
+    ///
+    /// This is the by-value form of [`raw_permissions`], for building in one
+    /// expression.
+    ///
+    /// [`raw_permissions`]: #method.raw_permissions
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_raw_permissions(mut self, permissions: u64) -> Self {
+        self.raw_permissions(permissions);
+
+        self
+    }
+
     /// Set the Redirect URI to redirect the user to.
     ///
     /// This will only be used if you [configure scopes] other than the
@@ -313,9 +756,38 @@ impl<'a> BotAuthorizationUrlBuilder<'a> {
         Ok(self)
     }
 
+    /// Set the Redirect URI to redirect the user to.
+    ///
+    /// This is the by-value form of [`redirect_uri`], for building in one
+    /// expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedirectUriInvalidError::Invalid`] if the provided redirect
+    /// URI isn't a valid URL.
+    ///
+    /// Returns [`RedirectUriInvalidError::Unconfigured`] if the provided
+    /// redirect URI isn't in the client's list of URIs.
+    ///
+    /// [`RedirectUriInvalidError::Invalid`]: ../client/enum.RedirectUriInvalidError.html#variant.Invalid
+    /// [`RedirectUriInvalidError::Unconfigured`]: ../client/enum.RedirectUriInvalidError.html#variant.Unconfigured
+    /// [`redirect_uri`]: #method.redirect_uri
+    pub fn with_redirect_uri(
+        mut self,
+        redirect_uri: &'a str,
+    ) -> Result<Self, RedirectUriInvalidError<'a>> {
+        let url = self.client.redirect_uri(redirect_uri)?;
+
+        self.redirect_uri.replace(url);
+
+        Ok(self)
+    }
+
     /// Set the scopes for the bot authorization request.
     ///
-    /// By default the [`Bot`] scope is selected.
+    /// By default the [`Bot`] scope is selected. Scopes or references to
+    /// scopes can be passed from any iterator; they're copied into a vector
+    /// owned by the builder.
     ///
     /// Read about Discord's [scope documentation].
     ///
@@ -324,26 +796,93 @@ impl<'a> BotAuthorizationUrlBuilder<'a> {
     /// [`Bot`]: enum.Scope.html#variant.Bot
     /// [RFC 6749 § 3.3]: https://tools.ietf.org/html/rfc6749#section-3.3
     /// [scope documentation]: https://discord.com/developers/docs/topics/oauth2#shared-resources-oauth2-scopes
-    pub fn scopes(&mut self, scopes: &'a [Scope]) -> &mut Self {
-        self.scopes = scopes;
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes = scopes.into_iter().map(|scope| *scope.borrow()).collect();
+
+        self
+    }
+
+    /// Set the scopes for the bot authorization request.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
+
+        self
+    }
+
+    /// Set the state for the bot authorization request.
+    ///
+    /// This is only returned to the redirect URI, so it's only useful along
+    /// with a [redirect URI].
+    ///
+    /// [redirect URI]: #method.redirect_uri
+    pub fn state(&mut self, state: &'a str) -> &mut Self {
+        self.state.replace(state);
+
+        self
+    }
+
+    /// Set the state for the bot authorization request.
+    ///
+    /// This is the by-value form of [`state`], for building in one
+    /// expression.
+    ///
+    /// [`state`]: #method.state
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_state(mut self, state: &'a str) -> Self {
+        self.state(state);
 
         self
     }
 }
 
+/// Write scopes separated by encoded spaces.
+fn write_scopes(url: &mut dyn FormWriter, scopes: &[Scope]) {
+    for (idx, scope) in scopes.iter().enumerate() {
+        if idx > 0 {
+            url.push_raw("%20");
+        }
+
+        url.push_raw(scope.name());
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AuthorizationUrlBuilder, BotAuthorizationUrlBuilder, Client, ResponseType, Scope};
+    use super::{
+        form, AuthorizationUrlBuilder, BotAuthorizationUrlBuilder, BuildError, Client,
+        CodeChallengeMethod, Prompt, ResponseType, Scope,
+    };
+    use crate::fixtures::ManualClock;
+    use crate::id::{ApplicationId, GuildId};
     use serde::{Deserialize, Serialize};
-    use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
-    use twilight_model::{
-        guild::Permissions,
-        id::{ApplicationId, GuildId},
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
     };
+    use url::Url;
 
     assert_impl_all!(AuthorizationUrlBuilder<'_>: Clone, Debug, Send, Sync);
     assert_impl_all!(BotAuthorizationUrlBuilder<'_>: Clone, Debug, Send, Sync);
+    assert_fields!(BuildError::UrlTooLong: length, limit);
+    assert_impl_all!(BuildError: Clone, Debug, Display, Eq, Error, PartialEq, Send, Sync);
+    assert_impl_all!(
+        CodeChallengeMethod: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Serialize,
+        Send,
+        Sync
+    );
     assert_impl_all!(
         ResponseType: Clone,
         Copy,
@@ -363,14 +902,13 @@ mod tests {
         let expected = "https://discord.com/api/oauth2/authorize?client_id=1&scope=bot";
         assert_eq!(expected, builder.build());
 
-        let perms = Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES;
-        builder.permissions(perms);
+        let perms = 0x800 | 0x2000;
+        builder.raw_permissions(perms);
         let expected = format!(
             "https://discord.com/api/oauth2/authorize?\
             client_id=1\
-            &permissions={}\
-            &scope=bot",
-            perms.bits()
+            &permissions={perms}\
+            &scope=bot"
         );
         assert_eq!(expected, builder.build());
 
@@ -379,9 +917,8 @@ mod tests {
             "https://discord.com/api/oauth2/authorize?\
             client_id=1\
             &guild_id=2\
-            &permissions={}\
-            &scope=bot",
-            perms.bits()
+            &permissions={perms}\
+            &scope=bot"
         );
         assert_eq!(expected, builder.build());
 
@@ -391,9 +928,8 @@ mod tests {
             client_id=1\
             &disable_guild_select=true\
             &guild_id=2\
-            &permissions={}\
-            &scope=bot",
-            perms.bits()
+            &permissions={perms}\
+            &scope=bot"
         );
         assert_eq!(expected, builder.build());
 
@@ -403,25 +939,208 @@ mod tests {
             client_id=1\
             &disable_guild_select=true\
             &guild_id=2\
-            &permissions={}\
+            &permissions={perms}\
             &redirect_uri=https%3A%2F%2Fexample.com%2F\
-            &scope=bot",
-            perms.bits()
+            &scope=bot"
         );
         assert_eq!(expected, builder.build());
 
-        builder.scopes(&[Scope::Bot, Scope::GuildsJoin]);
+        builder.scopes([Scope::Bot, Scope::GuildsJoin]);
         let expected = format!(
             "https://discord.com/api/oauth2/authorize?\
             client_id=1\
             &disable_guild_select=true\
             &guild_id=2\
-            &permissions={}\
+            &permissions={perms}\
             &redirect_uri=https%3A%2F%2Fexample.com%2F\
-            &scope=bot%20guilds.join",
-            perms.bits()
+            &scope=bot%20guilds.join"
+        );
+        assert_eq!(expected, builder.build());
+
+        builder.state("a b");
+        let expected = format!(
+            "https://discord.com/api/oauth2/authorize?\
+            client_id=1\
+            &disable_guild_select=true\
+            &guild_id=2\
+            &permissions={perms}\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F\
+            &scope=bot%20guilds.join\
+            &state=a%20b"
+        );
+        assert_eq!(expected, builder.build());
+    }
+
+    #[cfg(feature = "model")]
+    #[test]
+    fn test_bot_authorization_url_permissions() {
+        use twilight_model::guild::Permissions;
+
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let mut builder = client.bot_authorization_url();
+        builder.permissions(Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES);
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            client_id=1\
+            &permissions=10240\
+            &scope=bot";
+        assert_eq!(expected, builder.build());
+    }
+
+    #[test]
+    fn test_build_matches_incremental() {
+        fn incremental(
+            response_type: ResponseType,
+            scopes: &[Scope],
+            state: Option<&str>,
+            redirect_uri: &str,
+            prompt: Option<Prompt>,
+        ) -> String {
+            let mut url = Client::BASE_URI.to_owned();
+            url.push_str("?response_type=");
+            url.push_str(response_type.name());
+            url.push_str("&client_id=123456789012345678");
+
+            if !scopes.is_empty() {
+                url.push_str("&scope=");
+                let names = scopes.iter().map(Scope::name).collect::<Vec<_>>();
+                url.push_str(&names.join("%20"));
+            }
+
+            if let Some(state) = state {
+                url.push_str("&state=");
+                url.push_str(&form::build(|form| form.push_encoded(state)));
+            }
+
+            url.push_str("&redirect_uri=");
+            url.push_str(&form::build(|form| form.push_encoded(redirect_uri)));
+
+            if let Some(prompt) = prompt {
+                url.push_str("&prompt=");
+                url.push_str(prompt.name());
+            }
+
+            url
+        }
+
+        let redirect_uris = ["https://example.com/", "https://example.com/cb?a=b&c=d%20e"];
+        let client =
+            Client::new(ApplicationId(123_456_789_012_345_678), "a", &redirect_uris).unwrap();
+        let scopes: [&[Scope]; 3] = [&[], &[Scope::Identify], &[Scope::Identify, Scope::Guilds]];
+        let states = [None, Some("abc"), Some("a b+c/ü")];
+        let prompts = [None, Some(Prompt::Consent)];
+
+        for redirect_uri in &redirect_uris {
+            let mut builder = client.authorization_url(redirect_uri).unwrap();
+            let parsed = url::Url::parse(redirect_uri).unwrap();
+
+            for scopes in &scopes {
+                builder.scopes(*scopes);
+
+                for state in &states {
+                    if let Some(state) = state {
+                        builder.state(state);
+                    }
+
+                    for prompt in &prompts {
+                        if let Some(prompt) = prompt {
+                            builder.prompt(*prompt);
+                        }
+
+                        for response_type in &[ResponseType::Code, ResponseType::Token] {
+                            let url = builder.build_with_response_type(*response_type);
+                            let expected = incremental(
+                                *response_type,
+                                scopes,
+                                *state,
+                                parsed.as_str(),
+                                *prompt,
+                            );
+
+                            assert_eq!(expected, url);
+                            assert_eq!(url.len(), url.capacity());
+                        }
+
+                        builder.prompt = None;
+                    }
+
+                    builder.state = None;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_checked() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        let url = builder.build();
+        assert_eq!(url, builder.build_checked().unwrap());
+
+        let state = "a".repeat(AuthorizationUrlBuilder::DEFAULT_MAX_URL_LENGTH);
+        builder.state(&state);
+        let length = builder.build().len();
+        assert_eq!(
+            BuildError::UrlTooLong {
+                length,
+                limit: AuthorizationUrlBuilder::DEFAULT_MAX_URL_LENGTH,
+            },
+            builder.build_checked().unwrap_err()
+        );
+
+        builder.max_url_length(length);
+        assert_eq!(builder.build(), builder.build_checked().unwrap());
+    }
+
+    #[test]
+    fn test_default_scopes() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"])
+            .unwrap()
+            .with_default_scopes(vec![Scope::Identify]);
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &scope=identify\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F";
+        assert_eq!(expected, builder.build());
+
+        builder.add_scope(Scope::Email);
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &scope=identify%20email\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F";
+        assert_eq!(expected, builder.build());
+
+        builder.scopes([Scope::Guilds]);
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &scope=guilds\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F";
+        assert_eq!(expected, builder.build());
+    }
+
+    #[test]
+    fn test_scopes_iterator() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        builder.scopes(
+            Scope::all()
+                .iter()
+                .filter(|scope| scope.name().starts_with("guilds"))
+                .chain(&[Scope::Identify]),
         );
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &scope=guilds%20guilds.join%20guilds.members.read%20identify\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F";
         assert_eq!(expected, builder.build());
+
+        let mut bot = client.bot_authorization_url();
+        bot.scopes(vec![Scope::Bot, Scope::GuildsJoin].into_iter().rev());
+        assert!(bot.build().contains("&scope=guilds.join%20bot"));
     }
 
     #[test]
@@ -435,6 +1154,73 @@ mod tests {
         assert_eq!(expected, builder.implicit_grant());
     }
 
+    #[test]
+    fn test_redirect_uri_encoded() {
+        let redirect_uri = "https://example.com/cb?src=discord&a=b:c";
+        let client = Client::new(ApplicationId(1), "a", &[redirect_uri]).unwrap();
+        let builder = client.authorization_url(redirect_uri).unwrap();
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &redirect_uri=https%3A%2F%2Fexample.com%2Fcb%3Fsrc%3Ddiscord%26a%3Db%3Ac";
+        let url = builder.build();
+        assert_eq!(expected, url);
+
+        let parsed = url::Url::parse(&url).unwrap();
+        let mut pairs = parsed.query_pairs();
+        assert_eq!(
+            Some(("redirect_uri".into(), redirect_uri.into())),
+            pairs.find(|(key, _)| key == "redirect_uri")
+        );
+        assert!(parsed
+            .query_pairs()
+            .all(|(key, _)| key != "src" && key != "a"));
+
+        let mut builder = client.bot_authorization_url();
+        builder.scopes([Scope::Bot, Scope::Identify]);
+        builder.redirect_uri(redirect_uri).unwrap();
+        assert!(builder
+            .build()
+            .contains("&redirect_uri=https%3A%2F%2Fexample.com%2Fcb%3Fsrc%3Ddiscord%26a%3Db%3Ac&"));
+    }
+
+    #[test]
+    fn test_state_encoded() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        builder.state("a&b=c+d?e#f%g");
+        let expected = "https://discord.com/api/oauth2/authorize?\
+            response_type=code\
+            &client_id=1\
+            &state=a%26b%3Dc%2Bd%3Fe%23f%25g\
+            &redirect_uri=https%3A%2F%2Fexample.com%2F";
+        assert_eq!(expected, builder.build());
+    }
+
+    #[test]
+    fn test_write_url() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        let mut buf = "existing\n".to_owned();
+
+        builder.state("first");
+        let first = builder.build();
+        builder.write_url(&mut buf);
+        builder.state("second");
+        let second = builder.build();
+        builder.write_url(&mut buf);
+        assert_eq!(format!("existing\n{first}{second}"), buf);
+
+        let mut builder = client.bot_authorization_url();
+        let mut buf = String::new();
+        let first = builder.build();
+        builder.write_url(&mut buf);
+        builder.guild_id(GuildId(2));
+        let second = builder.build();
+        builder.write_url(&mut buf);
+        assert_eq!(format!("{first}{second}"), buf);
+    }
+
     #[test]
     fn test_webhook() {
         let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
@@ -446,4 +1232,171 @@ mod tests {
             &redirect_uri=https%3A%2F%2Fexample.com%2F";
         assert_eq!(expected, builder.webhook());
     }
+
+    #[test]
+    fn test_code_challenge() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let clock = ManualClock::new(std::time::UNIX_EPOCH);
+
+        let builder = client
+            .authorization_url("https://example.com/")
+            .unwrap()
+            .with_state("xyz")
+            .with_code_challenge("a+b", CodeChallengeMethod::S256);
+        assert!(builder
+            .build()
+            .ends_with("&code_challenge=a%2Bb&code_challenge_method=S256"));
+        assert_eq!(None, builder.session_with(&clock).unwrap().code_verifier);
+
+        let builder = builder.with_code_challenge("verifier", CodeChallengeMethod::Plain);
+        assert!(builder
+            .build()
+            .ends_with("&code_challenge=verifier&code_challenge_method=plain"));
+        assert_eq!(
+            Some("verifier"),
+            builder
+                .session_with(&clock)
+                .unwrap()
+                .code_verifier
+                .as_deref()
+        );
+
+        assert_eq!(
+            "\"plain\"",
+            serde_json::to_string(&CodeChallengeMethod::Plain).unwrap()
+        );
+        assert_eq!(
+            "\"S256\"",
+            serde_json::to_string(&CodeChallengeMethod::S256).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+
+        let mut builder = client.authorization_url("https://example.com/").unwrap();
+        builder
+            .code_challenge("verifier", CodeChallengeMethod::S256)
+            .max_url_length(100)
+            .prompt(Prompt::None)
+            .scopes([Scope::Identify])
+            .add_scope(Scope::Email)
+            .state("abc");
+        let fluent = client
+            .authorization_url("https://example.com/")
+            .unwrap()
+            .with_code_challenge("verifier", CodeChallengeMethod::S256)
+            .with_max_url_length(100)
+            .with_prompt(Prompt::None)
+            .with_scopes([Scope::Identify])
+            .with_scope(Scope::Email)
+            .with_state("abc");
+        assert_eq!(builder.build(), fluent.build());
+        assert_eq!(builder.build_checked(), fluent.build_checked());
+
+        let mut bot = client.bot_authorization_url();
+        bot.disable_guild_select(true)
+            .guild_id(GuildId(2))
+            .raw_permissions(8)
+            .scopes([Scope::Bot, Scope::Identify])
+            .redirect_uri("https://example.com/")
+            .unwrap();
+        let bot_fluent = client
+            .bot_authorization_url()
+            .with_disable_guild_select(true)
+            .with_guild_id(GuildId(2))
+            .with_raw_permissions(8)
+            .with_scopes([Scope::Bot, Scope::Identify])
+            .with_redirect_uri("https://example.com/")
+            .unwrap();
+        assert_eq!(bot.build(), bot_fluent.build());
+    }
+
+    /// Return the keys of the query of a URL, in order.
+    fn query_keys(url: &str) -> Vec<String> {
+        Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .map(|(key, _)| key.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_unset_options_omitted() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+
+        // Each option is set if its bit in the mask is.
+        for mask in 0..8_u8 {
+            let mut builder = client.authorization_url("https://example.com/").unwrap();
+
+            if mask & 1 != 0 {
+                builder.prompt(Prompt::Consent);
+            }
+
+            if mask & 2 != 0 {
+                builder.state("");
+            }
+
+            if mask & 4 != 0 {
+                builder.scopes([Scope::Identify]);
+            }
+
+            let keys = query_keys(&builder.build());
+            assert_eq!(mask & 1 != 0, keys.iter().any(|key| key == "prompt"));
+            assert_eq!(mask & 2 != 0, keys.iter().any(|key| key == "state"));
+            assert_eq!(mask & 4 != 0, keys.iter().any(|key| key == "scope"));
+            assert!(keys.iter().any(|key| key == "redirect_uri"));
+        }
+    }
+
+    #[test]
+    fn test_bot_unset_options_omitted() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/"]).unwrap();
+        let options = [
+            "disable_guild_select",
+            "guild_id",
+            "permissions",
+            "redirect_uri",
+            "state",
+        ];
+
+        // Each option is set, to the value Discord defaults to where there is
+        // one, if its bit in the mask is.
+        for mask in 0..32_u8 {
+            let mut builder = client.bot_authorization_url();
+
+            if mask & 1 != 0 {
+                builder.disable_guild_select(false);
+            }
+
+            if mask & 2 != 0 {
+                builder.guild_id(GuildId(2));
+            }
+
+            if mask & 4 != 0 {
+                builder.raw_permissions(0);
+            }
+
+            if mask & 8 != 0 {
+                builder.redirect_uri("https://example.com/").unwrap();
+            }
+
+            if mask & 16 != 0 {
+                builder.state("");
+            }
+
+            let keys = query_keys(&builder.build());
+
+            for (bit, option) in options.iter().enumerate() {
+                assert_eq!(mask & (1 << bit) != 0, keys.iter().any(|key| key == option));
+            }
+
+            assert!(keys.iter().any(|key| key == "scope"));
+        }
+
+        let mut builder = client.bot_authorization_url();
+        builder.scopes(Vec::<Scope>::new());
+        assert_eq!(vec!["client_id"], query_keys(&builder.build()));
+    }
 }
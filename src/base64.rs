@@ -0,0 +1,53 @@
+/// Standard base64 alphabet.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard base64, padded with `=`.
+///
+/// This is only used for HTTP Basic authentication, so it doesn't warrant a
+/// dependency.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut buf = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |group, (index, byte)| {
+                group | u32::from(*byte) << (16 - 8 * index)
+            });
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                buf.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                buf.push('=');
+            }
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode;
+
+    #[test]
+    fn test_encode() {
+        // Test vectors of RFC 4648, section 10.
+        for (value, expected) in &[
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(*expected, encode(value.as_bytes()));
+        }
+
+        assert_eq!("+/8=", encode(&[0xfb, 0xff]));
+    }
+}
@@ -0,0 +1,292 @@
+//! Add a bot to a guild via an authorization code grant.
+//!
+//! Adding a bot with a redirect URI also authorizes the user, returning an
+//! access token for them along with the guild the bot was added to. This
+//! combines the [bot authorization URL], the [access token exchange], and
+//! parsing of the guild from the response.
+//!
+//! This module is only available with the `model` feature enabled. Refer to
+//! [Discord's documentation] for additional information.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use twilight_model::guild::Permissions;
+//! use twilight_oauth2::{id::ApplicationId, Client, Scope};
+//!
+//! let client = Client::new(ApplicationId(1), "secret", &["https://example.com/cb"])?;
+//! let flow = client
+//!     .bot_authorization_flow("https://example.com/cb")
+//!     .expect("redirect uri is configured");
+//!
+//! let url = flow.authorization_url(Permissions::SEND_MESSAGES, [Scope::Email], "state");
+//! println!("add the bot at: {}", url);
+//!
+//! // After the user is redirected back with a code:
+//! let request = flow.exchange("code from the callback");
+//! println!("exchange url: {}", request.url());
+//! # Ok(()) }
+//! ```
+//!
+//! [Discord's documentation]: https://discord.com/developers/docs/topics/oauth2#advanced-bot-authorization
+//! [access token exchange]: ../request/access_token_exchange/index.html
+//! [bot authorization URL]: ../authorization_url/struct.BotAuthorizationUrlBuilder.html
+
+use super::{
+    request::access_token_exchange::{self, AccessTokenExchangeRequest},
+    AccessToken, Client, RefreshToken, Scope, TokenResponse, TokenType,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use twilight_model::guild::{PartialGuild, Permissions};
+use url::Url;
+
+/// Request exchanging the code of a bot authorization.
+///
+/// This is an [`AccessTokenExchangeRequest`]; its response is parsed as a
+/// [`BotAuthorization`].
+///
+/// [`AccessTokenExchangeRequest`]: ../request/access_token_exchange/struct.AccessTokenExchangeRequest.html
+/// [`BotAuthorization`]: struct.BotAuthorization.html
+pub type BotAuthorizationRequest<'a> = AccessTokenExchangeRequest<'a>;
+
+/// Flow adding a bot to a guild via an authorization code grant.
+///
+/// Both halves of the flow use the same redirect URI, as Discord requires.
+/// This is created via [`Client::bot_authorization_flow`].
+///
+/// [`Client::bot_authorization_flow`]: ../client/struct.Client.html#method.bot_authorization_flow
+#[derive(Clone, Debug)]
+pub struct BotAuthorizationFlow<'a> {
+    client: &'a Client,
+    redirect_uri: &'a Url,
+}
+
+impl<'a> BotAuthorizationFlow<'a> {
+    pub(crate) const fn new(client: &'a Client, redirect_uri: &'a Url) -> Self {
+        Self {
+            client,
+            redirect_uri,
+        }
+    }
+
+    /// Build the URL to send the user to for adding the bot.
+    ///
+    /// The [`Bot`] and [`Identify`] scopes are always requested, followed by
+    /// any extra scopes not already among them.
+    ///
+    /// [`Bot`]: ../enum.Scope.html#variant.Bot
+    /// [`Identify`]: ../enum.Scope.html#variant.Identify
+    pub fn authorization_url<T: Borrow<Scope>>(
+        &self,
+        permissions: Permissions,
+        extra_scopes: impl IntoIterator<Item = T>,
+        state: &str,
+    ) -> String {
+        let mut scopes = vec![Scope::Bot, Scope::Identify];
+
+        for scope in extra_scopes {
+            if !scopes.contains(scope.borrow()) {
+                scopes.push(*scope.borrow());
+            }
+        }
+
+        self.client
+            .bot_authorization_url()
+            .with_permissions(permissions)
+            .with_scopes(scopes)
+            .with_state(state)
+            .code_grant(self.redirect_uri)
+            .build()
+    }
+
+    /// Create the request exchanging the code received by the redirect URI.
+    ///
+    /// Parse the response as a [`BotAuthorization`].
+    ///
+    /// [`BotAuthorization`]: struct.BotAuthorization.html
+    pub fn exchange(&self, code: &'a str) -> BotAuthorizationRequest<'a> {
+        access_token_exchange::build(
+            self.client,
+            code,
+            None,
+            self.redirect_uri,
+            self.client.default_scopes(),
+        )
+    }
+
+    /// Return the redirect URI used by the flow.
+    pub const fn redirect_uri(&self) -> &Url {
+        self.redirect_uri
+    }
+}
+
+/// Response from exchanging the code of a bot authorization.
+///
+/// The guild the bot was added to is required, so deserializing the response
+/// of an exchange that didn't add a bot fails; that means the code came from
+/// another flow.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct BotAuthorization {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    pub access_token: AccessToken,
+    /// Number of seconds from issuing that the access token is valid.
+    ///
+    /// After this duration, the refresh token must be exchanged for another
+    /// access token and refresh token pair.
+    pub expires_in: u64,
+    /// Guild that the bot was added to.
+    pub guild: PartialGuild,
+    /// Refresh token to use to exchange for another access token and refresh
+    /// token pair.
+    pub refresh_token: RefreshToken,
+    /// Space-delimited list of scopes that the token has had approved.
+    pub scope: String,
+    /// Type of token provided.
+    ///
+    /// This will always be [`TokenType::Bearer`].
+    ///
+    /// [`TokenType::Bearer`]: ../enum.TokenType.html#variant.Bearer
+    pub token_type: TokenType,
+}
+
+#[cfg(feature = "json")]
+impl BotAuthorization {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error, including when it has no guild.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl TokenResponse for BotAuthorization {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        Some(&self.refresh_token)
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BotAuthorization, BotAuthorizationFlow};
+    use crate::{
+        fixtures::{self, ACCESS_TOKEN_RESPONSE, BOT_AUTHORIZATION_RESPONSE},
+        id::ApplicationId,
+        request::OAuthRequest,
+        Client, Scope,
+    };
+    use serde::{Deserialize, Serialize};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::fmt::Debug;
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(
+        BotAuthorization: access_token,
+        expires_in,
+        guild,
+        refresh_token,
+        scope,
+        token_type
+    );
+    assert_impl_all!(BotAuthorizationFlow<'_>: Clone, Debug, Send, Sync);
+    assert_impl_all!(
+        BotAuthorization: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    fn client() -> Client {
+        Client::new(ApplicationId(1), "a", &["https://example.com/cb"]).unwrap()
+    }
+
+    #[test]
+    fn test_authorization_url() {
+        let client = client();
+        let flow = client
+            .bot_authorization_flow("https://example.com/cb")
+            .unwrap();
+        let url = flow.authorization_url(
+            Permissions::SEND_MESSAGES,
+            [Scope::Identify, Scope::Email],
+            "a state",
+        );
+
+        assert_eq!(
+            "https://discord.com/api/oauth2/authorize?\
+            client_id=1\
+            &permissions=2048\
+            &redirect_uri=https%3A%2F%2Fexample.com%2Fcb\
+            &response_type=code\
+            &scope=bot%20identify%20email\
+            &state=a%20state",
+            url
+        );
+    }
+
+    #[test]
+    fn test_exchange() {
+        let client = client();
+        let flow = client
+            .bot_authorization_flow("https://example.com/cb")
+            .unwrap();
+        let expected = client
+            .access_token_exchange("code", "https://example.com/cb")
+            .unwrap()
+            .build();
+
+        assert_eq!(expected, flow.exchange("code"));
+        assert_eq!(
+            "client_id=1&client_secret=a&code=code\
+            &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&scope=",
+            flow.exchange("code").body().unwrap()
+        );
+        assert_eq!("https://example.com/cb", flow.redirect_uri().as_str());
+        assert!(client.bot_authorization_flow("https://other.com").is_err());
+    }
+
+    #[test]
+    fn test_response() {
+        let authorization =
+            serde_json::from_slice::<BotAuthorization>(BOT_AUTHORIZATION_RESPONSE).unwrap();
+        assert_eq!(fixtures::bot_authorization_response(), authorization);
+        assert_eq!("test guild", authorization.guild.name);
+
+        // Responses of other flows have no guild.
+        assert!(serde_json::from_slice::<BotAuthorization>(ACCESS_TOKEN_RESPONSE).is_err());
+    }
+}
@@ -4,14 +4,21 @@ use super::{
         access_token_exchange::AccessTokenExchangeBuilder,
         client_credentials_grant::ClientCredentialsGrantBuilder,
         refresh_token_exchange::RefreshTokenExchangeBuilder,
+        token_introspection::IntrospectionBuilder, token_revocation::TokenRevocationBuilder,
+        Headers, CONTENT_TYPE, FORM_CONTENT_TYPE, HEADERS, USER_AGENT,
     },
+    session_store::AuthorizationSession,
+    Scope,
 };
+#[cfg(feature = "model")]
+use crate::bot_authorization::BotAuthorizationFlow;
+use crate::{base64, form, id::ApplicationId};
 use std::{
+    borrow::Cow,
     error::Error,
-    fmt::{Display, Formatter, Result as FmtResult},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
 };
-use twilight_model::id::ApplicationId;
-use url::{ParseError, Url};
+use url::{Host, ParseError, Url};
 
 /// Creating a client failed due to misconfiguration.
 ///
@@ -21,6 +28,12 @@ use url::{ParseError, Url};
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum CreateClientError<'a> {
+    /// Client secret is empty or contains characters other than printable
+    /// ASCII.
+    ClientSecretInvalid {
+        /// Reason the client secret is invalid.
+        reason: String,
+    },
     /// Redirect URI is not a valid URL.
     RedirectUriInvalid {
         /// Reason for the error.
@@ -28,6 +41,16 @@ pub enum CreateClientError<'a> {
         /// Provided URI.
         uri: &'a str,
     },
+    /// Redirect URI is a valid URL, but can't be used as a redirect URI.
+    ///
+    /// This is the case if it has a fragment, or if it uses `http` on a host
+    /// other than a loopback address.
+    RedirectUriUnsupported {
+        /// Reason the redirect URI can't be used.
+        reason: String,
+        /// Provided URI.
+        uri: &'a str,
+    },
 }
 
 impl Display for CreateClientError<'_> {
@@ -35,6 +58,9 @@ impl Display for CreateClientError<'_> {
         f.write_str("creating oauth2 client failed: ")?;
 
         match self {
+            Self::ClientSecretInvalid { reason } | Self::RedirectUriUnsupported { reason, .. } => {
+                f.write_str(reason)
+            }
             Self::RedirectUriInvalid { source, .. } => Display::fmt(source, f),
         }
     }
@@ -43,6 +69,7 @@ impl Display for CreateClientError<'_> {
 impl Error for CreateClientError<'_> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            Self::ClientSecretInvalid { .. } | Self::RedirectUriUnsupported { .. } => None,
             Self::RedirectUriInvalid { source, .. } => Some(source),
         }
     }
@@ -71,8 +98,7 @@ impl Display for RedirectUriInvalidError<'_> {
         match self {
             Self::Invalid { source, .. } => Display::fmt(source, f),
             Self::Unconfigured { uri } => f.write_fmt(format_args!(
-                "the provided uri ('{}') is not configured in the client",
-                uri,
+                "the provided uri ('{uri}') is not configured in the client",
             )),
         }
     }
@@ -87,11 +113,16 @@ impl Error for RedirectUriInvalidError<'_> {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Client {
     client_id: ApplicationId,
     client_secret: String,
+    default_scopes: Vec<Scope>,
     redirect_uris: Vec<Url>,
+    /// Text of the redirect URIs sent in requests, by index of the parsed
+    /// URIs.
+    redirect_uri_texts: Vec<String>,
+    user_agent: Option<Cow<'static, str>>,
 }
 
 impl Client {
@@ -100,34 +131,206 @@ impl Client {
 
     /// Create a new client with application information.
     ///
+    /// Redirect URIs may use custom schemes, such as `myapp://oauth/callback`
+    /// for mobile and desktop applications. They're sent in requests exactly
+    /// as provided here, including the case of the scheme. `http` and
+    /// `https` URIs are sent in their normalized form, such as
+    /// `https://example.com/` for `https://example.com`.
+    ///
     /// # Errors
     ///
+    /// Returns [`CreateClientError::ClientSecretInvalid`] if the client secret
+    /// is empty or contains characters other than printable ASCII.
+    ///
     /// Returns [`CreateClientError::RedirectUriInvalid`] if any of the provided
     /// redirect URIs are invalid URLs.
     ///
+    /// Returns [`CreateClientError::RedirectUriUnsupported`] if any of the
+    /// provided redirect URIs has a fragment, or uses `http` on a host other
+    /// than a loopback address.
+    ///
+    /// [`CreateClientError::ClientSecretInvalid`]: enum.CreateClientError.html#variant.ClientSecretInvalid
     /// [`CreateClientError::RedirectUriInvalid`]: enum.CreateClientError.html#variant.RedirectUriInvalid
+    /// [`CreateClientError::RedirectUriUnsupported`]: enum.CreateClientError.html#variant.RedirectUriUnsupported
     pub fn new<'a>(
         client_id: ApplicationId,
         client_secret: impl Into<String>,
         redirect_uris: &'a [&'a str],
     ) -> Result<Self, CreateClientError<'a>> {
+        let client_secret = client_secret.into();
+        Self::validate_secret(&client_secret)?;
+
         let iter = redirect_uris.iter();
         let mut uris = iter.size_hint().1.map_or_else(Vec::new, Vec::with_capacity);
+        let mut texts = Vec::with_capacity(uris.capacity());
 
         for item in iter {
             let uri = Url::parse(item)
                 .map_err(|source| CreateClientError::RedirectUriInvalid { source, uri: item })?;
+            Self::validate_redirect_uri(&uri).map_err(|reason| {
+                CreateClientError::RedirectUriUnsupported { reason, uri: item }
+            })?;
+
+            let text = match uri.scheme() {
+                "http" | "https" => uri.as_str(),
+                _ => item.trim_matches(|c: char| c <= ' '),
+            };
 
+            texts.push(text.to_owned());
             uris.push(uri);
         }
 
         Ok(Self {
             client_id,
-            client_secret: client_secret.into(),
+            client_secret,
+            default_scopes: Vec::new(),
+            redirect_uri_texts: texts,
             redirect_uris: uris,
+            user_agent: None,
         })
     }
 
+    /// Replace the client secret, such as after rotating it in the developer
+    /// portal.
+    ///
+    /// Requests built after this use the new secret. The secret is validated
+    /// like in [`new`], and left unchanged if it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CreateClientError::ClientSecretInvalid`] if the client secret
+    /// is empty or contains characters other than printable ASCII.
+    ///
+    /// [`CreateClientError::ClientSecretInvalid`]: enum.CreateClientError.html#variant.ClientSecretInvalid
+    /// [`new`]: #method.new
+    pub fn rotate_secret(
+        &mut self,
+        new_secret: impl Into<String>,
+    ) -> Result<(), CreateClientError<'static>> {
+        let new_secret = new_secret.into();
+        Self::validate_secret(&new_secret)?;
+
+        self.client_secret = new_secret;
+
+        Ok(())
+    }
+
+    /// Validate that a redirect URI has no fragment and only uses `http` on a
+    /// loopback address.
+    fn validate_redirect_uri(uri: &Url) -> Result<(), String> {
+        if uri.fragment().is_some() {
+            return Err("redirect uri has a fragment".to_owned());
+        }
+
+        let loopback = match uri.host() {
+            Some(Host::Domain(domain)) => domain == "localhost",
+            Some(Host::Ipv4(address)) => address.is_loopback(),
+            Some(Host::Ipv6(address)) => address.is_loopback(),
+            None => false,
+        };
+
+        if uri.scheme() == "http" && !loopback {
+            return Err(
+                "redirect uri uses http on a host other than a loopback address".to_owned(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validate that a client secret is non-empty printable ASCII.
+    fn validate_secret(client_secret: &str) -> Result<(), CreateClientError<'static>> {
+        if client_secret.is_empty() {
+            return Err(CreateClientError::ClientSecretInvalid {
+                reason: "client secret is empty".to_owned(),
+            });
+        }
+
+        if let Some(position) = client_secret
+            .bytes()
+            .position(|byte| !(0x20..=0x7e).contains(&byte))
+        {
+            return Err(CreateClientError::ClientSecretInvalid {
+                reason: format!(
+                    "client secret contains a non-printable or non-ascii character at byte {position}"
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Set the scopes that builders created from the client use by default.
+    ///
+    /// Scopes are resolved in the following order:
+    ///
+    /// 1. Scopes set on a builder via its `scopes` method replace all other
+    ///    scopes;
+    /// 2. Otherwise, the client's default scopes are used;
+    /// 3. Otherwise, the builder's own default is used, such as [`Identify`]
+    ///    for client credentials grants.
+    ///
+    /// Scopes added on a builder via its `add_scope` method are appended to
+    /// whichever of these is in effect.
+    ///
+    /// The defaults apply to authorization URLs, access token exchanges, and
+    /// client credentials grants. Bot authorization URLs always default to
+    /// the [`Bot`] scope and refresh token exchanges to no scopes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client, Scope};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?
+    ///     .with_default_scopes(vec![Scope::Identify, Scope::Guilds]);
+    ///
+    /// let request = client.client_credentials_grant().with_scope(Scope::Email).build();
+    ///
+    /// assert_eq!("identify guilds email", request.body.scope);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Bot`]: ../enum.Scope.html#variant.Bot
+    /// [`Identify`]: ../enum.Scope.html#variant.Identify
+    #[must_use = "the client is returned with the default scopes set"]
+    pub fn with_default_scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.default_scopes = scopes;
+
+        self
+    }
+
+    /// Set the `User-Agent` header sent with requests built from the client.
+    ///
+    /// Defaults to [`USER_AGENT`]. Discord asks that the header identifies
+    /// the library or application making the request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, request::OAuthRequest, Client};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?
+    ///     .with_user_agent("DiscordBot (https://example.com, 1.0.0)");
+    ///
+    /// let request = client.client_credentials_grant().build();
+    /// assert!(request
+    ///     .headers()
+    ///     .iter()
+    ///     .any(|(name, value)| name == "User-Agent" && value.starts_with("DiscordBot (https://example.com")));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`USER_AGENT`]: request/constant.USER_AGENT.html
+    #[must_use = "the client is returned with the user agent set"]
+    pub fn with_user_agent(mut self, user_agent: impl Into<Cow<'static, str>>) -> Self {
+        self.user_agent.replace(user_agent.into());
+
+        self
+    }
+
     /// Return a builder to create a URL for bot authorization.
     ///
     /// # Examples
@@ -137,23 +340,55 @@ impl Client {
     ///
     /// ```
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use twilight_model::{guild::Permissions, id::ApplicationId};
-    /// use twilight_oauth2::Client;
+    /// use twilight_oauth2::{id::ApplicationId, Client};
     ///
     /// let application_id = ApplicationId(123);
     /// let client_secret = "abcdef01234567890";
     ///
     /// let client = Client::new(application_id, client_secret, &["https://example.com"])?;
-    /// let mut url_builder = client.bot_authorization_url();
-    /// url_builder.permissions(Permissions::SEND_MESSAGES);
+    /// let url = client
+    ///     .bot_authorization_url()
+    ///     .with_raw_permissions(0x800)
+    ///     .build();
     ///
-    /// println!("bot authorization url: {}", url_builder.build());
+    /// println!("bot authorization url: {}", url);
     /// # Ok(()) }
     /// ```
     pub fn bot_authorization_url(&self) -> BotAuthorizationUrlBuilder<'_> {
         BotAuthorizationUrlBuilder::new(self)
     }
 
+    /// Create a flow for adding a bot to a guild via an authorization code
+    /// grant.
+    ///
+    /// The provided redirect URI is used both for the authorization URL and
+    /// the code exchange. It must be in the client's list of configured
+    /// redirect URIs. Refer to the [`bot_authorization`] module for more
+    /// information.
+    ///
+    /// This is only available with the `model` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedirectUriInvalidError::Invalid`] if the provided redirect
+    /// URI isn't a valid URL.
+    ///
+    /// Returns [`RedirectUriInvalidError::Unconfigured`] if the provided
+    /// redirect URI isn't in the client's list of URIs.
+    ///
+    /// [`RedirectUriInvalidError::Invalid`]: enum.RedirectUriInvalidError.html#variant.Invalid
+    /// [`RedirectUriInvalidError::Unconfigured`]: enum.RedirectUriInvalidError.html#variant.Unconfigured
+    /// [`bot_authorization`]: ../bot_authorization/index.html
+    #[cfg(feature = "model")]
+    pub fn bot_authorization_flow<'a>(
+        &'a self,
+        redirect_uri: &'a str,
+    ) -> Result<BotAuthorizationFlow<'a>, RedirectUriInvalidError<'a>> {
+        let redirect_uri = self.redirect_uri(redirect_uri)?;
+
+        Ok(BotAuthorizationFlow::new(self, redirect_uri))
+    }
+
     /// Create a new Authorization URL builder.
     ///
     /// The provided redirect URI is what the user will be redirected to after
@@ -168,6 +403,25 @@ impl Client {
     /// Returns [`RedirectUriInvalidError::Unconfigured`] if the provided
     /// redirect URI isn't in the client's list of URIs.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client, Scope};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+    ///
+    /// let url = client
+    ///     .authorization_url("https://example.com")
+    ///     .expect("redirect uri is configured")
+    ///     .with_scopes([Scope::Identify])
+    ///     .with_state("random state")
+    ///     .build();
+    ///
+    /// println!("authorization url: {}", url);
+    /// # Ok(()) }
+    /// ```
+    ///
     /// [`RedirectUriInvalidError::Invalid`]: enum.RedirectUriInvalidError.html#variant.Invalid
     /// [`RedirectUriInvalidError::Unconfigured`]: enum.RedirectUriInvalidError.html#variant.Unconfigured
     pub fn authorization_url<'a>(
@@ -177,6 +431,42 @@ impl Client {
         AuthorizationUrlBuilder::new(self, redirect_uri)
     }
 
+    /// Create an access token exchange request.
+    ///
+    /// The provided code is the authorization code received by the redirect
+    /// URI after the user approved authorization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedirectUriInvalidError::Invalid`] if the provided redirect
+    /// URI isn't a valid URL.
+    ///
+    /// Returns [`RedirectUriInvalidError::Unconfigured`] if the provided
+    /// redirect URI isn't in the client's list of URIs.
+    ///
+    /// # Examples
+    ///
+    /// Create the request in one expression from the code received by the
+    /// redirect URI:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client, Scope};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+    ///
+    /// let request = client
+    ///     .access_token_exchange("code", "https://example.com")
+    ///     .expect("redirect uri is configured")
+    ///     .with_scopes([Scope::Identify])
+    ///     .build();
+    ///
+    /// println!("exchange url: {}", request.url());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`RedirectUriInvalidError::Invalid`]: enum.RedirectUriInvalidError.html#variant.Invalid
+    /// [`RedirectUriInvalidError::Unconfigured`]: enum.RedirectUriInvalidError.html#variant.Unconfigured
     pub fn access_token_exchange<'a>(
         &'a self,
         code: &'a str,
@@ -185,6 +475,47 @@ impl Client {
         AccessTokenExchangeBuilder::new(self, code, redirect_uri)
     }
 
+    /// Create an access token exchange request for the code of a pending
+    /// authorization session.
+    ///
+    /// The redirect URI and code verifier are taken from the session. Refer
+    /// to the [`session_store`] module for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedirectUriInvalidError`] if the session's redirect URI is
+    /// not one of the client's configured redirect URIs.
+    ///
+    /// [`RedirectUriInvalidError`]: struct.RedirectUriInvalidError.html
+    /// [`session_store`]: ../session_store/index.html
+    pub fn session_exchange<'a>(
+        &'a self,
+        code: &'a str,
+        session: &'a AuthorizationSession,
+    ) -> Result<AccessTokenExchangeBuilder<'a>, RedirectUriInvalidError<'a>> {
+        let mut builder = self.access_token_exchange(code, &session.redirect_uri)?;
+
+        if let Some(code_verifier) = session.code_verifier.as_deref() {
+            builder.code_verifier(code_verifier);
+        }
+
+        Ok(builder)
+    }
+
+    /// Create a refresh token exchange request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+    /// let request = client.refresh_token_exchange("refresh token").build();
+    ///
+    /// println!("refresh url: {}", request.url());
+    /// # Ok(()) }
+    /// ```
     pub fn refresh_token_exchange<'a>(
         &'a self,
         refresh_token: &'a str,
@@ -192,6 +523,29 @@ impl Client {
         RefreshTokenExchangeBuilder::new(self, refresh_token)
     }
 
+    /// Create a request to introspect an access token or refresh token at an
+    /// introspection endpoint.
+    ///
+    /// Discord doesn't publicly document an introspection endpoint, so the
+    /// endpoint of the authorization server that issued the token has to be
+    /// provided.
+    ///
+    /// Refer to the [`token_introspection`] module for more information.
+    ///
+    /// [`token_introspection`]: ../request/token_introspection/index.html
+    pub fn introspect_token<'a>(
+        &'a self,
+        endpoint: &'a str,
+        token: &'a str,
+    ) -> IntrospectionBuilder<'a> {
+        IntrospectionBuilder::new(self, endpoint, token)
+    }
+
+    /// Create a request to revoke an access token or refresh token.
+    pub fn token_revocation<'a>(&'a self, token: &'a str) -> TokenRevocationBuilder<'a> {
+        TokenRevocationBuilder::new(self, token)
+    }
+
     /// Create a client credentials grant request.
     ///
     /// A client credentials grant can be used to quickly create bearer tokens
@@ -201,50 +555,260 @@ impl Client {
         ClientCredentialsGrantBuilder::new(self)
     }
 
+    /// Validate the state received in an authorization callback against the
+    /// expected state.
+    ///
+    /// This should always be called before processing a callback and before
+    /// exchanging its authorization code. The comparison takes the same
+    /// amount of time regardless of where the strings differ, which prevents
+    /// the expected state from being guessed via timing attacks. Only the
+    /// length of the expected state can be inferred.
+    ///
+    /// Read about Discord's recommendations for [state and security] for more
+    /// information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Client;
+    ///
+    /// assert!(Client::validate_callback_state("abc", "abc"));
+    /// assert!(!Client::validate_callback_state("abd", "abc"));
+    /// ```
+    ///
+    /// [state and security]: https://discord.com/developers/docs/topics/oauth2#state-and-security
+    pub fn validate_callback_state(received: &str, expected: &str) -> bool {
+        let received = received.as_bytes();
+        let expected = expected.as_bytes();
+
+        let mut diff = u8::from(received.len() != expected.len());
+
+        for (idx, byte) in expected.iter().enumerate() {
+            diff |= byte ^ received.get(idx).copied().unwrap_or_default();
+        }
+
+        diff == 0
+    }
+
     /// Return an immutable reference to the configured client ID.
     pub fn client_id(&self) -> ApplicationId {
         self.client_id
     }
 
+    /// Return the configured client ID formatted as a string.
+    ///
+    /// This is useful for metrics labels and log fields that require an
+    /// owned string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client};
+    ///
+    /// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+    /// assert_eq!("123", client.application_id_string());
+    /// # Ok(()) }
+    /// ```
+    pub fn application_id_string(&self) -> String {
+        self.client_id.to_string()
+    }
+
     /// Return an immutable reference to the configured client secret.
     pub fn client_secret(&self) -> &str {
         self.client_secret.as_ref()
     }
 
+    /// Return the value of the `Authorization` header authenticating the
+    /// client via HTTP Basic authentication.
+    ///
+    /// The client ID and secret are urlencoded before being joined and
+    /// base64-encoded, as [RFC 6749] requires. The [client credentials grant]
+    /// sends this header instead of the credentials in its body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client};
+    ///
+    /// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+    /// assert_eq!("Basic MTpzZWNyZXQ=", client.basic_authorization());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [RFC 6749]: https://tools.ietf.org/html/rfc6749#section-2.3.1
+    /// [client credentials grant]: #method.client_credentials_grant
+    pub fn basic_authorization(&self) -> String {
+        let credentials = form::build(|form| {
+            form.push_number(self.client_id.0);
+            form.push_raw(":");
+            form.push_encoded(&self.client_secret);
+        });
+
+        let mut value = String::from("Basic ");
+        value.push_str(&base64::encode(credentials.as_bytes()));
+
+        value
+    }
+
+    /// Return an immutable reference to the configured default scopes.
+    pub fn default_scopes(&self) -> &[Scope] {
+        self.default_scopes.as_ref()
+    }
+
     /// Return an immutable reference to the configured redirect URIs.
     pub fn redirect_uris(&self) -> &[Url] {
         self.redirect_uris.as_ref()
     }
 
+    /// Return the `User-Agent` header sent with requests.
+    ///
+    /// This is [`USER_AGENT`] unless configured via [`with_user_agent`].
+    ///
+    /// [`USER_AGENT`]: request/constant.USER_AGENT.html
+    /// [`with_user_agent`]: #method.with_user_agent
+    pub fn user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(USER_AGENT)
+    }
+
+    /// Return the headers to send with requests.
+    ///
+    /// The default headers are borrowed, so requests only allocate for them
+    /// when the user agent is configured.
+    pub(crate) fn headers(&self) -> Headers {
+        match &self.user_agent {
+            Some(user_agent) => Cow::Owned(vec![
+                (
+                    Cow::Borrowed(CONTENT_TYPE),
+                    Cow::Borrowed(FORM_CONTENT_TYPE),
+                ),
+                (Cow::Borrowed("User-Agent"), user_agent.clone()),
+            ]),
+            None => Cow::Borrowed(HEADERS),
+        }
+    }
+
+    /// Return the headers to send with requests authenticating the client via
+    /// HTTP Basic authentication.
+    ///
+    /// These are the [`headers`] with an `Authorization` header added.
+    ///
+    /// [`headers`]: #method.headers
+    pub(crate) fn basic_auth_headers(&self) -> Headers {
+        let mut headers = self.headers().into_owned();
+        headers.push((
+            Cow::Borrowed("Authorization"),
+            Cow::Owned(self.basic_authorization()),
+        ));
+
+        Cow::Owned(headers)
+    }
+
+    /// Return the text sent in requests for a configured redirect URI.
+    ///
+    /// This is the URI as provided when creating the client for custom
+    /// schemes, and the normalized URI otherwise.
+    pub(crate) fn redirect_uri_text<'a>(&'a self, uri: &'a Url) -> &'a str {
+        self.redirect_uris
+            .iter()
+            .position(|configured| configured == uri)
+            .map_or(uri.as_str(), |index| &self.redirect_uri_texts[index])
+    }
+
     pub(crate) fn redirect_uri<'a>(
         &'a self,
         redirect_uri: &'a str,
     ) -> Result<&'a Url, RedirectUriInvalidError<'a>> {
-        let url = Url::parse(redirect_uri).map_err(|source| RedirectUriInvalidError::Invalid {
-            source,
-            uri: redirect_uri,
+        let url = Url::parse(redirect_uri).map_err(|source| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                application_id = self.client_id.0,
+                error = %source,
+                "redirect uri is invalid",
+            );
+
+            RedirectUriInvalidError::Invalid {
+                source,
+                uri: redirect_uri,
+            }
         })?;
 
-        self.redirect_uris()
+        let result = self
+            .redirect_uris()
             .iter()
             .find(|uri| **uri == url)
-            .ok_or_else(|| RedirectUriInvalidError::Unconfigured { uri: url })
+            .ok_or(RedirectUriInvalidError::Unconfigured { uri: url });
+
+        #[cfg(feature = "tracing")]
+        if let Err(source) = &result {
+            tracing::warn!(
+                application_id = self.client_id.0,
+                error = %source,
+                "redirect uri is not configured",
+            );
+        }
+
+        result
+    }
+}
+
+/// Display the client's application ID, suitable for logging.
+///
+/// The client secret is never displayed.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{id::ApplicationId, Client};
+///
+/// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+/// assert_eq!("OAuth2Client(id=123)", client.to_string());
+/// # Ok(()) }
+/// ```
+impl Display for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("OAuth2Client(id=")?;
+        Display::fmt(&self.client_id, f)?;
+
+        f.write_str(")")
+    }
+}
+
+/// The client secret is redacted, so that clients embedded in errors and
+/// pools can be logged.
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Client")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &format_args!("<redacted>"))
+            .field("default_scopes", &self.default_scopes)
+            .field("redirect_uris", &self.redirect_uris)
+            .field("redirect_uri_texts", &self.redirect_uri_texts)
+            .field("user_agent", &self.user_agent)
+            .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Client, CreateClientError, RedirectUriInvalidError};
+    use crate::id::ApplicationId;
+    use crate::request::{OAuthRequest, USER_AGENT};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{
+        borrow::Cow,
         error::Error,
         fmt::{Debug, Display},
+        thread,
     };
-    use twilight_model::id::ApplicationId;
     use url::ParseError;
 
-    assert_impl_all!(Client: Clone, Debug, Send, Sync);
+    assert_impl_all!(Client: Clone, Debug, Display, Send, Sync);
+    assert_fields!(CreateClientError::ClientSecretInvalid: reason);
     assert_fields!(CreateClientError::RedirectUriInvalid: source, uri);
+    assert_fields!(CreateClientError::RedirectUriUnsupported: reason, uri);
     assert_impl_all!(CreateClientError<'_>: Clone, Debug, Display, Eq, Error, PartialEq, Send, Sync);
     assert_fields!(RedirectUriInvalidError::Invalid: source, uri);
     assert_fields!(RedirectUriInvalidError::Unconfigured: uri);
@@ -264,6 +828,138 @@ mod tests {
         assert_eq!(["https://example.com/"], uris.as_slice());
     }
 
+    #[test]
+    fn test_client_display() {
+        let client = Client::new(ApplicationId(1), "secret", &["https://example.com"]).unwrap();
+
+        let displayed = client.to_string();
+        assert_eq!("OAuth2Client(id=1)", displayed);
+        assert!(!displayed.contains("secret"));
+    }
+
+    #[test]
+    fn test_client_debug_redacted() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let debug = format!("{client:?}");
+
+        assert!(debug.contains("client_secret: <redacted>"));
+        assert!(debug.contains("https://example.com"));
+        assert!(!debug.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_client_application_id_string() {
+        let client = Client::new(ApplicationId(123), "secret", &["https://example.com"]).unwrap();
+
+        assert_eq!(
+            format!("{}", client.client_id().0),
+            client.application_id_string()
+        );
+        assert_eq!(
+            client.client_id().to_string(),
+            client.application_id_string()
+        );
+    }
+
+    #[test]
+    fn test_client_rotate_secret() {
+        let mut client = Client::new(ApplicationId(1), "old", &["https://example.com"]).unwrap();
+        client.rotate_secret("new").unwrap();
+        assert_eq!("new", client.client_secret());
+
+        let request = client.client_credentials_grant().build();
+        assert_eq!("new", request.body.client_secret);
+
+        assert!(matches!(
+            client.rotate_secret(""),
+            Err(CreateClientError::ClientSecretInvalid { .. })
+        ));
+        assert_eq!("new", client.client_secret());
+    }
+
+    #[test]
+    fn test_client_create_client_secret_invalid() {
+        assert!(matches!(
+            Client::new(ApplicationId(1), "", &[]),
+            Err(CreateClientError::ClientSecretInvalid { .. })
+        ));
+        assert!(matches!(
+            Client::new(ApplicationId(1), "abc\0def", &[]),
+            Err(CreateClientError::ClientSecretInvalid { .. })
+        ));
+        assert!(matches!(
+            Client::new(ApplicationId(1), "abc\ndef", &[]),
+            Err(CreateClientError::ClientSecretInvalid { .. })
+        ));
+        assert!(matches!(
+            Client::new(ApplicationId(1), "abcdéf", &[]),
+            Err(CreateClientError::ClientSecretInvalid { .. })
+        ));
+        assert!(Client::new(ApplicationId(1), "aZ09-_aZ09-_aZ09-_aZ09-_aZ09-_aZ", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_client_headers() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        assert_eq!(USER_AGENT, client.user_agent());
+        assert!(matches!(client.headers(), Cow::Borrowed(_)));
+
+        let client = client.with_user_agent("DiscordBot (https://example.com, 1.0.0)");
+        assert_eq!(
+            "DiscordBot (https://example.com, 1.0.0)",
+            client.user_agent()
+        );
+        assert_eq!(
+            client.headers(),
+            [
+                (
+                    "Content-Type".into(),
+                    "application/x-www-form-urlencoded".into()
+                ),
+                (
+                    "User-Agent".into(),
+                    "DiscordBot (https://example.com, 1.0.0)".into()
+                ),
+            ]
+            .as_ref()
+        );
+    }
+
+    #[test]
+    fn test_client_basic_authorization() {
+        let client = Client::new(ApplicationId(1), "a", &[]).unwrap();
+        assert_eq!("Basic MTph", client.basic_authorization());
+
+        // The secret is urlencoded before it's base64-encoded.
+        let client = Client::new(ApplicationId(123), "a:b c", &[]).unwrap();
+        assert_eq!(
+            format!("Basic {}", super::base64::encode(b"123:a%3Ab%20c")),
+            client.basic_authorization()
+        );
+    }
+
+    #[test]
+    fn test_client_owned_secret() {
+        let secret = format!("{}-{}", "a", 1);
+        let client = Client::new(ApplicationId(1), secret, &["https://example.com"]).unwrap();
+
+        let secret = thread::spawn(move || client.client_secret().to_owned())
+            .join()
+            .unwrap();
+        assert_eq!("a-1", secret);
+    }
+
+    #[test]
+    fn test_validate_callback_state() {
+        assert!(Client::validate_callback_state("", ""));
+        assert!(Client::validate_callback_state("abc123", "abc123"));
+        assert!(!Client::validate_callback_state("abc124", "abc123"));
+        assert!(!Client::validate_callback_state("abc", "abc123"));
+        assert!(!Client::validate_callback_state("abc123abc", "abc123"));
+        assert!(!Client::validate_callback_state("", "abc123"));
+        assert!(!Client::validate_callback_state("abc\0\0\0", "abc"));
+    }
+
     #[test]
     fn test_client_create_redirect_uri_invalid() {
         let actual = Client::new(ApplicationId(1), "a", &["b"]).unwrap_err();
@@ -273,4 +969,64 @@ mod tests {
             uri,
         } if source == ParseError::RelativeUrlWithoutBase && uri == "b"));
     }
+
+    #[test]
+    fn test_client_create_redirect_uri_unsupported() {
+        for uri in &[
+            "https://example.com/cb#fragment",
+            "myapp://callback#",
+            "http://example.com/cb",
+        ] {
+            assert!(matches!(
+                Client::new(ApplicationId(1), "a", &[uri]),
+                Err(CreateClientError::RedirectUriUnsupported { uri: actual, .. }) if actual == *uri
+            ));
+        }
+
+        assert!(Client::new(
+            ApplicationId(1),
+            "a",
+            &[
+                "http://localhost:8080/",
+                "http://127.0.0.1:8080/",
+                "http://[::1]:8080/",
+                "https://example.com/cb",
+            ],
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_custom_scheme_round_trip() {
+        for uri in &[
+            "myapp://oauth/callback",
+            "MyApp://callback",
+            "com.example.App:/oauth2redirect",
+            "myapp:callback",
+        ] {
+            let client = Client::new(ApplicationId(1), "a", &[uri]).unwrap();
+            let encoded = url::form_urlencoded::byte_serialize(uri.as_bytes()).collect::<String>();
+
+            let url = client.authorization_url(uri).unwrap().build();
+            assert!(url.ends_with(&format!("&redirect_uri={encoded}")));
+
+            let request = client.access_token_exchange("code", uri).unwrap().build();
+            assert_eq!(*uri, request.body.redirect_uri);
+            assert!(request
+                .body()
+                .unwrap()
+                .contains(&format!("&redirect_uri={encoded}&")));
+
+            let refresh = client.refresh_token_exchange("refresh").build();
+            assert_eq!(*uri, refresh.body.redirect_uri);
+        }
+
+        // Schemes are case insensitive, but the configured case is sent.
+        let client = Client::new(ApplicationId(1), "a", &["MyApp://callback"]).unwrap();
+        let request = client
+            .access_token_exchange("code", "myapp://callback")
+            .unwrap()
+            .build();
+        assert_eq!("MyApp://callback", request.body.redirect_uri);
+    }
 }
@@ -0,0 +1,342 @@
+//! Pool of clients for operating multiple applications.
+
+use crate::{id::ApplicationId, Client};
+use std::{
+    collections::{hash_map::Values, HashMap},
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use url::Url;
+
+/// Inserting a client into a [`ClientPool`] failed.
+///
+/// The rejected client is returned in each variant.
+///
+/// [`ClientPool`]: struct.ClientPool.html
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InsertClientError {
+    /// A client for the same application is already in the pool.
+    Duplicate {
+        /// Client that was rejected.
+        client: Box<Client>,
+    },
+    /// A redirect URI of the client is already configured for another client
+    /// in the pool, so redirects to it couldn't be routed.
+    RedirectUriTaken {
+        /// Client that was rejected.
+        client: Box<Client>,
+        /// ID of the application already owning the redirect URI.
+        owner: ApplicationId,
+        /// Redirect URI that is already taken.
+        uri: Url,
+    },
+}
+
+impl Display for InsertClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Duplicate { client } => {
+                f.write_str("a client for application ")?;
+                Display::fmt(&client.client_id(), f)?;
+
+                f.write_str(" is already in the pool")
+            }
+            Self::RedirectUriTaken { owner, uri, .. } => {
+                f.write_str("redirect uri ")?;
+                f.write_str(uri.as_str())?;
+                f.write_str(" is already configured for application ")?;
+
+                Display::fmt(owner, f)
+            }
+        }
+    }
+}
+
+impl Error for InsertClientError {}
+
+/// Pool of clients for multiple applications, keyed by application ID.
+///
+/// Besides looking clients up by application ID, the client owning a redirect
+/// URI can be found via [`client_for_redirect`], which routes callbacks when
+/// the callback doesn't otherwise identify the application. Redirect URIs
+/// are therefore unique across the pool.
+///
+/// Cloning the pool clones every client, including its secret and redirect
+/// URIs, so wrap the pool in an [`Arc`] to share it.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{client_pool::ClientPool, id::ApplicationId, Client};
+///
+/// let mut pool = ClientPool::new();
+/// pool.insert(Client::new(ApplicationId(1), "a", &["https://one.example.com/cb"])?)?;
+/// pool.insert(Client::new(ApplicationId(2), "b", &["https://two.example.com/cb"])?)?;
+///
+/// let client = pool.client_for_redirect("https://two.example.com/cb");
+/// assert_eq!(Some(ApplicationId(2)), client.map(Client::client_id));
+/// # Ok(()) }
+/// ```
+///
+/// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+/// [`client_for_redirect`]: #method.client_for_redirect
+#[derive(Clone, Debug, Default)]
+pub struct ClientPool {
+    clients: HashMap<ApplicationId, Client>,
+}
+
+impl ClientPool {
+    /// Create a new, empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a client into the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsertClientError::Duplicate`] if a client for the same
+    /// application is already in the pool. Use [`remove`] first to replace
+    /// it.
+    ///
+    /// Returns [`InsertClientError::RedirectUriTaken`] if one of the client's
+    /// redirect URIs is already configured for another client.
+    ///
+    /// [`InsertClientError::Duplicate`]: enum.InsertClientError.html#variant.Duplicate
+    /// [`InsertClientError::RedirectUriTaken`]: enum.InsertClientError.html#variant.RedirectUriTaken
+    /// [`remove`]: #method.remove
+    pub fn insert(&mut self, client: Client) -> Result<(), InsertClientError> {
+        if self.clients.contains_key(&client.client_id()) {
+            return Err(InsertClientError::Duplicate {
+                client: Box::new(client),
+            });
+        }
+
+        let taken = client.redirect_uris().iter().find_map(|uri| {
+            self.owner_of(uri)
+                .map(|owner| (owner.client_id(), uri.clone()))
+        });
+
+        if let Some((owner, uri)) = taken {
+            return Err(InsertClientError::RedirectUriTaken {
+                client: Box::new(client),
+                owner,
+                uri,
+            });
+        }
+
+        self.clients.insert(client.client_id(), client);
+
+        Ok(())
+    }
+
+    /// Return an immutable reference to the client of an application.
+    pub fn get(&self, application_id: ApplicationId) -> Option<&Client> {
+        self.clients.get(&application_id)
+    }
+
+    /// Remove the client of an application, returning it if it was in the
+    /// pool.
+    pub fn remove(&mut self, application_id: ApplicationId) -> Option<Client> {
+        self.clients.remove(&application_id)
+    }
+
+    /// Return the client configured with a redirect URI.
+    ///
+    /// The URI is compared to the configured redirect URIs after parsing, so
+    /// it must match one exactly, including its path. Returns `None` if the
+    /// URI is invalid or no client is configured with it.
+    pub fn client_for_redirect(&self, redirect_uri: &str) -> Option<&Client> {
+        let url = Url::parse(redirect_uri).ok()?;
+
+        self.owner_of(&url)
+    }
+
+    /// Return an iterator over the clients in the pool, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.clients.values(),
+        }
+    }
+
+    /// Return the number of clients in the pool.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Return whether the pool has no clients.
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    fn owner_of(&self, url: &Url) -> Option<&Client> {
+        self.clients
+            .values()
+            .find(|client| client.redirect_uris().contains(url))
+    }
+}
+
+impl<'a> IntoIterator for &'a ClientPool {
+    type IntoIter = Iter<'a>;
+    type Item = &'a Client;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the clients of a [`ClientPool`].
+///
+/// This is created via [`ClientPool::iter`].
+///
+/// [`ClientPool`]: struct.ClientPool.html
+/// [`ClientPool::iter`]: struct.ClientPool.html#method.iter
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: Values<'a, ApplicationId, Client>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Client;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientPool, InsertClientError, Iter};
+    use crate::{id::ApplicationId, Client};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+    };
+
+    assert_fields!(InsertClientError::Duplicate: client);
+    assert_fields!(InsertClientError::RedirectUriTaken: client, owner, uri);
+    assert_impl_all!(ClientPool: Clone, Debug, Default, Send, Sync);
+    assert_impl_all!(InsertClientError: Debug, Display, Error, Send, Sync);
+    assert_impl_all!(Iter<'_>: Clone, Debug, ExactSizeIterator, Send, Sync);
+
+    fn client(id: u64, redirect_uris: &[&str]) -> Client {
+        Client::new(ApplicationId(id), "secret", redirect_uris).unwrap()
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let mut pool = ClientPool::new();
+        pool.insert(client(1, &["https://example.com/1"])).unwrap();
+        let error = pool
+            .insert(client(1, &["https://example.com/other"]))
+            .unwrap_err();
+
+        for debug in &[format!("{pool:?}"), format!("{error:?}")] {
+            assert!(debug.contains("<redacted>"));
+            assert!(!debug.contains("\"secret\""));
+        }
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let mut pool = ClientPool::new();
+        pool.insert(client(1, &["https://example.com/1"])).unwrap();
+
+        match pool.insert(client(1, &["https://example.com/other"])) {
+            Err(InsertClientError::Duplicate { client }) => {
+                assert_eq!(ApplicationId(1), client.client_id());
+                assert_eq!(
+                    "https://example.com/other",
+                    client.redirect_uris()[0].as_str()
+                );
+            }
+            _ => panic!("expected a duplicate error"),
+        }
+
+        assert_eq!(1, pool.len());
+        assert_eq!(
+            "https://example.com/1",
+            pool.get(ApplicationId(1)).unwrap().redirect_uris()[0].as_str()
+        );
+    }
+
+    #[test]
+    fn test_redirect_uri_taken() {
+        let mut pool = ClientPool::new();
+        pool.insert(client(1, &["https://example.com/cb"])).unwrap();
+
+        let error = pool
+            .insert(client(
+                2,
+                &["https://example.com/2", "https://example.com/cb"],
+            ))
+            .unwrap_err();
+        assert_eq!(
+            "redirect uri https://example.com/cb is already configured for application 1",
+            error.to_string()
+        );
+        assert!(pool.get(ApplicationId(2)).is_none());
+    }
+
+    #[test]
+    fn test_client_for_redirect() {
+        let mut pool = ClientPool::new();
+        pool.insert(client(1, &["https://example.com/a/callback"]))
+            .unwrap();
+        pool.insert(client(
+            2,
+            &["https://example.com/b/callback", "http://localhost:8080/"],
+        ))
+        .unwrap();
+        pool.insert(client(3, &["https://sub.example.com/a/callback"]))
+            .unwrap();
+
+        let lookup = |uri| pool.client_for_redirect(uri).map(Client::client_id);
+        assert_eq!(
+            Some(ApplicationId(1)),
+            lookup("https://example.com/a/callback")
+        );
+        assert_eq!(
+            Some(ApplicationId(2)),
+            lookup("https://example.com/b/callback")
+        );
+        assert_eq!(Some(ApplicationId(2)), lookup("http://localhost:8080"));
+        assert_eq!(
+            Some(ApplicationId(3)),
+            lookup("https://sub.example.com/a/callback")
+        );
+        assert_eq!(None, lookup("https://example.com/c/callback"));
+        assert_eq!(None, lookup("https://example.com/a/callback/extra"));
+        assert_eq!(None, lookup("not a url"));
+    }
+
+    #[test]
+    fn test_remove_and_iter() {
+        let mut pool = ClientPool::new();
+        assert!(pool.is_empty());
+        pool.insert(client(1, &["https://example.com/1"])).unwrap();
+        pool.insert(client(2, &["https://example.com/2"])).unwrap();
+
+        let mut ids = pool.iter().map(Client::client_id).collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(vec![ApplicationId(1), ApplicationId(2)], ids);
+        assert_eq!(2, (&pool).into_iter().len());
+
+        let removed = pool.remove(ApplicationId(1)).unwrap();
+        assert_eq!(ApplicationId(1), removed.client_id());
+        assert!(pool.remove(ApplicationId(1)).is_none());
+        assert!(pool.client_for_redirect("https://example.com/1").is_none());
+
+        pool.insert(client(3, &["https://example.com/1"])).unwrap();
+        assert_eq!(2, pool.len());
+    }
+}
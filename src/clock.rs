@@ -0,0 +1,63 @@
+use std::time::SystemTime;
+
+/// Source of the current time for expiry calculations.
+///
+/// Methods that depend on the current time have a variant accepting a clock,
+/// so tests can control time instead of relying on the system clock. The
+/// variants without a clock use [`SystemClock`], and aren't available on
+/// `wasm32-unknown-unknown`, which has no system clock.
+///
+/// With the `test-util` feature enabled, a [`ManualClock`] that is advanced
+/// by hand is available.
+///
+/// [`ManualClock`]: fixtures/struct.ManualClock.html
+/// [`SystemClock`]: struct.SystemClock.html
+pub trait Clock {
+    /// Return the current time.
+    fn now(&self) -> SystemTime;
+}
+
+impl<T: Clock + ?Sized> Clock for &T {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
+
+/// Clock returning the current time of the system.
+///
+/// This doesn't implement [`Clock`] on `wasm32-unknown-unknown`, where reading
+/// the system time panics. Implement [`Clock`] with a time source of the
+/// environment, such as `Date.now()` in the browser, instead.
+///
+/// [`Clock`]: trait.Clock.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SystemClock;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, SystemClock};
+    use static_assertions::assert_impl_all;
+    use std::{
+        fmt::Debug,
+        time::{Duration, SystemTime},
+    };
+
+    assert_impl_all!(SystemClock: Clock, Clone, Copy, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(&SystemClock: Clock);
+
+    #[test]
+    fn test_system_clock() {
+        let before = SystemTime::now();
+        let now = SystemClock.now();
+
+        assert!(now >= before);
+        assert!(now.duration_since(before).unwrap() < Duration::from_secs(59));
+    }
+}
@@ -0,0 +1,457 @@
+//! Realistic response fixtures for testing code that uses this crate.
+//!
+//! Each fixture is available both as a typed value and as the JSON body
+//! Discord would send for it, so tests of callback handlers and token
+//! storage don't need hand-written payloads. The typed value is always what
+//! deserializing the JSON body produces.
+//!
+//! A [`ManualClock`] is also provided for testing expiry logic without
+//! depending on the system clock.
+//!
+//! This module is only available with the `test-util` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use twilight_oauth2::fixtures;
+//!
+//! let response = fixtures::access_token_response();
+//! assert_eq!("identify", response.scope);
+//! assert!(fixtures::ACCESS_TOKEN_RESPONSE.starts_with(b"{"));
+//! ```
+//!
+//! [`ManualClock`]: struct.ManualClock.html
+
+#[cfg(feature = "model")]
+use super::{
+    bot_authorization::BotAuthorization,
+    request::webhook_token_exchange::WebhookTokenExchangeResponse,
+};
+use super::{
+    request::{
+        access_token_exchange::AccessTokenExchangeResponse,
+        client_credentials_grant::ClientCredentialsGrantResponse,
+        refresh_token_exchange::RefreshTokenExchangeResponse,
+    },
+    AccessToken, Clock, RefreshToken, TokenType,
+};
+#[cfg(feature = "model")]
+use std::collections::HashMap;
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+#[cfg(feature = "model")]
+use twilight_model::{
+    channel::{Webhook, WebhookType},
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, PartialGuild,
+        Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, GuildId, RoleId, UserId, WebhookId},
+};
+
+/// JSON body of an access token exchange response.
+///
+/// Deserializes to [`access_token_response`].
+///
+/// [`access_token_response`]: fn.access_token_response.html
+pub const ACCESS_TOKEN_RESPONSE: &[u8] = br#"{
+    "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+    "token_type": "Bearer",
+    "expires_in": 604800,
+    "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+    "scope": "identify"
+}"#;
+
+/// JSON body of the code exchange response of a bot authorization.
+///
+/// Deserializes to [`bot_authorization_response`].
+///
+/// [`bot_authorization_response`]: fn.bot_authorization_response.html
+#[cfg(feature = "model")]
+pub const BOT_AUTHORIZATION_RESPONSE: &[u8] = br#"{
+    "access_token": "zMndOe7jFLXGawdlxMOdNvXjjOce5X",
+    "token_type": "Bearer",
+    "expires_in": 604800,
+    "refresh_token": "mgp8qnvBwJcmadwgCYKyYD5CAzGAX4",
+    "scope": "bot identify",
+    "guild": {
+        "id": "290926798626357250",
+        "name": "test guild",
+        "icon": null,
+        "splash": null,
+        "discovery_splash": null,
+        "banner": null,
+        "description": null,
+        "owner_id": "53908232506183680",
+        "application_id": null,
+        "region": "us-east",
+        "afk_channel_id": null,
+        "afk_timeout": 300,
+        "verification_level": 0,
+        "default_message_notifications": 0,
+        "explicit_content_filter": 0,
+        "roles": [
+            {
+                "id": "290926798626357250",
+                "name": "@everyone",
+                "color": 0,
+                "hoist": false,
+                "position": 0,
+                "permissions": "104324673",
+                "managed": false,
+                "mentionable": false
+            }
+        ],
+        "emojis": [],
+        "features": [],
+        "mfa_level": 0,
+        "system_channel_id": null,
+        "system_channel_flags": 0,
+        "rules_channel_id": null,
+        "vanity_url_code": null,
+        "premium_tier": 0,
+        "preferred_locale": "en-US"
+    }
+}"#;
+
+/// JSON body of a client credentials grant response.
+///
+/// Deserializes to [`client_credentials_grant_response`].
+///
+/// [`client_credentials_grant_response`]: fn.client_credentials_grant_response.html
+pub const CLIENT_CREDENTIALS_GRANT_RESPONSE: &[u8] = br#"{
+    "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+    "token_type": "Bearer",
+    "expires_in": 604800,
+    "scope": "identify connections"
+}"#;
+
+/// JSON body of a refresh token exchange response.
+///
+/// Deserializes to [`refresh_token_response`].
+///
+/// [`refresh_token_response`]: fn.refresh_token_response.html
+pub const REFRESH_TOKEN_RESPONSE: &[u8] = br#"{
+    "access_token": "Pb2fJ1XM8lA5Ow3fU0JMT2Wr6BLgPl",
+    "token_type": "Bearer",
+    "expires_in": 604800,
+    "refresh_token": "x7eP5sUjcX9hRRSKW4mMZ6pJxvr1u9",
+    "scope": "identify"
+}"#;
+
+/// JSON body of a webhook token exchange response.
+///
+/// Deserializes to [`webhook_token_response`].
+///
+/// [`webhook_token_response`]: fn.webhook_token_response.html
+#[cfg(feature = "model")]
+pub const WEBHOOK_TOKEN_RESPONSE: &[u8] = br#"{
+    "token_type": "Bearer",
+    "access_token": "GNaVzEtATqdh173tNHEXY9ZYAuhiYxvy",
+    "scope": "webhook.incoming",
+    "expires_in": 604800,
+    "refresh_token": "PvPL7ELyMDc1836457XCDh1Y8jPbRm",
+    "webhook": {
+        "application_id": "310954232226357250",
+        "name": "testwebhook",
+        "url": "https://discord.com/api/webhooks/347114750880120863/kKDdjXa1g9tKNs0-_yOwLyALC9gydEWP6gr9sHabuK1vuofjhQDDnlOclJeRIvYK-pj_",
+        "channel_id": "345626669224982402",
+        "token": "kKDdjXa1g9tKNs0-_yOwLyALC9gydEWP6gr9sHabuK1vuofjhQDDnlOclJeRIvYK-pj_",
+        "type": 1,
+        "avatar": null,
+        "guild_id": "290926792226357250",
+        "id": "347114750880120863"
+    }
+}"#;
+
+/// Typed access token exchange response.
+///
+/// This is the deserialized form of [`ACCESS_TOKEN_RESPONSE`].
+///
+/// [`ACCESS_TOKEN_RESPONSE`]: constant.ACCESS_TOKEN_RESPONSE.html
+pub fn access_token_response() -> AccessTokenExchangeResponse {
+    AccessTokenExchangeResponse {
+        access_token: AccessToken::new("6qrZcUqja7812RVdnEKjpzOL4CvHBFG"),
+        expires_in: 604_800,
+        refresh_token: RefreshToken::new("D43f5y0ahjqew82jZ4NViEr2YafMKhue"),
+        scope: "identify".to_owned(),
+        token_type: TokenType::Bearer,
+        #[cfg(feature = "model")]
+        webhook: None,
+    }
+}
+
+/// Typed code exchange response of a bot authorization.
+///
+/// This is the deserialized form of [`BOT_AUTHORIZATION_RESPONSE`].
+///
+/// [`BOT_AUTHORIZATION_RESPONSE`]: constant.BOT_AUTHORIZATION_RESPONSE.html
+#[cfg(feature = "model")]
+pub fn bot_authorization_response() -> BotAuthorization {
+    let everyone = Role {
+        color: 0,
+        hoist: false,
+        id: RoleId(290_926_798_626_357_250),
+        managed: false,
+        mentionable: false,
+        name: "@everyone".to_owned(),
+        permissions: Permissions::from_bits_truncate(104_324_673),
+        position: 0,
+        tags: None,
+    };
+
+    let mut roles = HashMap::new();
+    roles.insert(everyone.id, everyone);
+
+    BotAuthorization {
+        access_token: AccessToken::new("zMndOe7jFLXGawdlxMOdNvXjjOce5X"),
+        expires_in: 604_800,
+        guild: PartialGuild {
+            id: GuildId(290_926_798_626_357_250),
+            afk_channel_id: None,
+            afk_timeout: 300,
+            application_id: None,
+            banner: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            description: None,
+            discovery_splash: None,
+            emojis: HashMap::new(),
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: Vec::new(),
+            icon: None,
+            max_members: None,
+            max_presences: None,
+            member_count: None,
+            mfa_level: MfaLevel::None,
+            name: "test guild".to_owned(),
+            owner_id: UserId(53_908_232_506_183_680),
+            owner: None,
+            permissions: None,
+            preferred_locale: "en-US".to_owned(),
+            premium_subscription_count: None,
+            premium_tier: PremiumTier::None,
+            region: "us-east".to_owned(),
+            roles,
+            rules_channel_id: None,
+            splash: None,
+            system_channel_flags: SystemChannelFlags::empty(),
+            system_channel_id: None,
+            verification_level: VerificationLevel::None,
+            vanity_url_code: None,
+            widget_channel_id: None,
+            widget_enabled: None,
+        },
+        refresh_token: RefreshToken::new("mgp8qnvBwJcmadwgCYKyYD5CAzGAX4"),
+        scope: "bot identify".to_owned(),
+        token_type: TokenType::Bearer,
+    }
+}
+
+/// Typed client credentials grant response.
+///
+/// This is the deserialized form of [`CLIENT_CREDENTIALS_GRANT_RESPONSE`].
+///
+/// [`CLIENT_CREDENTIALS_GRANT_RESPONSE`]: constant.CLIENT_CREDENTIALS_GRANT_RESPONSE.html
+pub fn client_credentials_grant_response() -> ClientCredentialsGrantResponse {
+    ClientCredentialsGrantResponse {
+        access_token: AccessToken::new("6qrZcUqja7812RVdnEKjpzOL4CvHBFG"),
+        expires_in: 604_800,
+        token_type: TokenType::Bearer,
+        scope: "identify connections".to_owned(),
+    }
+}
+
+/// Typed refresh token exchange response.
+///
+/// This is the deserialized form of [`REFRESH_TOKEN_RESPONSE`].
+///
+/// [`REFRESH_TOKEN_RESPONSE`]: constant.REFRESH_TOKEN_RESPONSE.html
+pub fn refresh_token_response() -> RefreshTokenExchangeResponse {
+    RefreshTokenExchangeResponse {
+        access_token: AccessToken::new("Pb2fJ1XM8lA5Ow3fU0JMT2Wr6BLgPl"),
+        expires_in: 604_800,
+        refresh_token: RefreshToken::new("x7eP5sUjcX9hRRSKW4mMZ6pJxvr1u9"),
+        scope: "identify".to_owned(),
+        token_type: TokenType::Bearer,
+    }
+}
+
+/// Typed webhook token exchange response.
+///
+/// This is the deserialized form of [`WEBHOOK_TOKEN_RESPONSE`].
+///
+/// [`WEBHOOK_TOKEN_RESPONSE`]: constant.WEBHOOK_TOKEN_RESPONSE.html
+#[cfg(feature = "model")]
+pub fn webhook_token_response() -> WebhookTokenExchangeResponse {
+    WebhookTokenExchangeResponse {
+        access_token: AccessToken::new("GNaVzEtATqdh173tNHEXY9ZYAuhiYxvy"),
+        expires_in: 604_800,
+        refresh_token: RefreshToken::new("PvPL7ELyMDc1836457XCDh1Y8jPbRm"),
+        scope: "webhook.incoming".to_owned(),
+        token_type: TokenType::Bearer,
+        webhook: Webhook {
+            avatar: None,
+            channel_id: ChannelId(345_626_669_224_982_402),
+            guild_id: Some(GuildId(290_926_792_226_357_250)),
+            id: WebhookId(347_114_750_880_120_863),
+            kind: WebhookType::Incoming,
+            name: Some("testwebhook".to_owned()),
+            token: Some(
+                "kKDdjXa1g9tKNs0-_yOwLyALC9gydEWP6gr9sHabuK1vuofjhQDDnlOclJeRIvYK-pj_".to_owned(),
+            ),
+            user: None,
+        },
+    }
+}
+
+/// Clock that only moves when told to.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use twilight_oauth2::{fixtures::ManualClock, Clock, StoredToken};
+///
+/// let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000));
+/// let token = StoredToken::new("access", 1_030, None, "identify");
+/// assert_eq!(Duration::from_secs(30), token.remaining_with(&clock));
+///
+/// clock.advance(Duration::from_secs(30));
+/// assert_eq!(Duration::from_secs(0), token.remaining_with(&clock));
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+impl ManualClock {
+    /// Create a new clock stopped at a point in time.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move the clock forward by an amount of time.
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(PoisonError::into_inner);
+        *now += duration;
+    }
+
+    /// Set the clock to a point in time.
+    pub fn set(&self, time: SystemTime) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) = time;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        access_token_response, client_credentials_grant_response, refresh_token_response,
+        ManualClock, ACCESS_TOKEN_RESPONSE, CLIENT_CREDENTIALS_GRANT_RESPONSE,
+        REFRESH_TOKEN_RESPONSE,
+    };
+    #[cfg(feature = "model")]
+    use super::{
+        bot_authorization_response, webhook_token_response, BOT_AUTHORIZATION_RESPONSE,
+        WEBHOOK_TOKEN_RESPONSE,
+    };
+    #[cfg(feature = "model")]
+    use crate::request::{
+        access_token_exchange::AccessTokenExchangeResponse, exchange_response::ExchangeResponse,
+    };
+    use crate::Clock;
+    use static_assertions::assert_impl_all;
+    use std::{
+        fmt::Debug,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    assert_impl_all!(ManualClock: Clock, Debug, Send, Sync);
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        assert_eq!(UNIX_EPOCH, clock.now());
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(5), clock.now());
+
+        clock.set(UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(1), clock.now());
+    }
+
+    #[test]
+    fn test_fixtures_match() {
+        assert_eq!(
+            access_token_response(),
+            serde_json::from_slice(ACCESS_TOKEN_RESPONSE).unwrap()
+        );
+        assert_eq!(
+            client_credentials_grant_response(),
+            serde_json::from_slice(CLIENT_CREDENTIALS_GRANT_RESPONSE).unwrap()
+        );
+        assert_eq!(
+            refresh_token_response(),
+            serde_json::from_slice(REFRESH_TOKEN_RESPONSE).unwrap()
+        );
+        #[cfg(feature = "model")]
+        assert_eq!(
+            webhook_token_response(),
+            serde_json::from_slice(WEBHOOK_TOKEN_RESPONSE).unwrap()
+        );
+        #[cfg(feature = "model")]
+        assert_eq!(
+            bot_authorization_response(),
+            serde_json::from_slice(BOT_AUTHORIZATION_RESPONSE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let debug = [
+            format!("{:?}", access_token_response()),
+            format!("{:?}", client_credentials_grant_response()),
+            format!("{:?}", refresh_token_response()),
+        ];
+        #[cfg(feature = "model")]
+        let debug = [
+            &debug[..],
+            &[
+                format!("{:?}", webhook_token_response()),
+                format!("{:?}", bot_authorization_response()),
+                format!(
+                    "{:?}",
+                    AccessTokenExchangeResponse {
+                        webhook: Some(webhook_token_response().webhook),
+                        ..access_token_response()
+                    }
+                ),
+                format!("{:?}", ExchangeResponse::from(webhook_token_response())),
+            ],
+        ]
+        .concat();
+
+        for token in &[
+            "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+            "Pb2fJ1XM8lA5Ow3fU0JMT2Wr6BLgPl",
+            "x7eP5sUjcX9hRRSKW4mMZ6pJxvr1u9",
+            "GNaVzEtATqdh173tNHEXY9ZYAuhiYxvy",
+            "PvPL7ELyMDc1836457XCDh1Y8jPbRm",
+            "zMndOe7jFLXGawdlxMOdNvXjjOce5X",
+            "mgp8qnvBwJcmadwgCYKyYD5CAzGAX4",
+            "kKDdjXa1g9tKNs0-_yOwLyALC9gydEWP6gr9sHabuK1vuofjhQDDnlOclJeRIvYK-pj_",
+        ] {
+            assert!(debug.iter().all(|debug| !debug.contains(token)));
+        }
+    }
+}
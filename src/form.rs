@@ -0,0 +1,185 @@
+use std::fmt::Write;
+
+/// Destination of a urlencoded form.
+///
+/// Values are percent-encoded with every byte except unreserved characters
+/// (ASCII alphanumerics, `-`, `.`, `_`, and `~`) becoming `%XX`. Spaces are
+/// encoded as `%20` rather than `+`, in both URL queries and form bodies, so
+/// that the two are byte-identical.
+///
+/// Forms are written twice by [`append`]: once to compute the exact length
+/// and once into a string with that much capacity reserved.
+///
+/// [`append`]: fn.append.html
+pub(crate) trait FormWriter {
+    /// Push a value that is already urlencoded.
+    fn push_raw(&mut self, value: &str);
+
+    /// Push a value, urlencoding it.
+    fn push_encoded(&mut self, value: &str);
+
+    /// Push a number.
+    fn push_number(&mut self, value: u64);
+}
+
+/// Length of a form, computed without writing it.
+struct FormLength(usize);
+
+impl FormWriter for FormLength {
+    fn push_raw(&mut self, value: &str) {
+        self.0 += value.len();
+    }
+
+    fn push_encoded(&mut self, value: &str) {
+        self.0 += encoded_len(value);
+    }
+
+    fn push_number(&mut self, mut value: u64) {
+        self.0 += 1;
+
+        while value >= 10 {
+            value /= 10;
+            self.0 += 1;
+        }
+    }
+}
+
+impl FormWriter for String {
+    fn push_raw(&mut self, value: &str) {
+        self.push_str(value);
+    }
+
+    fn push_encoded(&mut self, value: &str) {
+        let mut start = 0;
+
+        for (index, byte) in value.bytes().enumerate() {
+            if is_unreserved(byte) {
+                continue;
+            }
+
+            // Unreserved characters are ASCII, so a non-empty run of them
+            // starts and ends on character boundaries.
+            if start < index {
+                self.push_str(&value[start..index]);
+            }
+
+            self.push('%');
+            self.push(char::from(HEX[usize::from(byte >> 4)]));
+            self.push(char::from(HEX[usize::from(byte & 0xf)]));
+            start = index + 1;
+        }
+
+        self.push_str(&value[start..]);
+    }
+
+    fn push_number(&mut self, value: u64) {
+        let _ = write!(self, "{value}");
+    }
+}
+
+/// Build a string written by a function, allocating it once with the exact
+/// capacity.
+pub(crate) fn build(write: impl Fn(&mut dyn FormWriter)) -> String {
+    let mut buf = String::new();
+    append(&mut buf, write);
+
+    buf
+}
+
+/// Append what a function writes to a buffer, reserving the exact additional
+/// capacity up front.
+pub(crate) fn append(buf: &mut String, write: impl Fn(&mut dyn FormWriter)) {
+    let mut length = FormLength(0);
+    write(&mut length);
+
+    buf.reserve_exact(length.0);
+    write(buf);
+}
+
+/// Uppercase hexadecimal digits for percent-encoding.
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Whether a byte is kept as is when urlencoding.
+const fn is_unreserved(byte: u8) -> bool {
+    matches!(byte, b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' | b'-' | b'.' | b'_' | b'~')
+}
+
+/// Length of a value once urlencoded.
+///
+/// Unreserved characters are kept as is and every other byte becomes a
+/// three character percent-encoding.
+fn encoded_len(value: &str) -> usize {
+    value
+        .bytes()
+        .map(|byte| if is_unreserved(byte) { 1 } else { 3 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, encoded_len, FormLength, FormWriter};
+
+    #[test]
+    fn test_encoded_len() {
+        for value in &[
+            "",
+            "abc",
+            "a b+c%d",
+            "https://example.com/cb?source=discord",
+            "-._~",
+            "ünïcödé",
+        ] {
+            let encoded = build(|form| form.push_encoded(value));
+
+            assert_eq!(encoded.len(), encoded_len(value));
+        }
+    }
+
+    #[test]
+    fn test_encoded() {
+        for (value, expected) in &[
+            ("", ""),
+            ("abcXYZ019-._~", "abcXYZ019-._~"),
+            ("a b", "a%20b"),
+            ("a+b", "a%2Bb"),
+            ("a&b=c", "a%26b%3Dc"),
+            ("?#%/:", "%3F%23%25%2F%3A"),
+            ("ü", "%C3%BC"),
+            ("日本", "%E6%97%A5%E6%9C%AC"),
+            ("\u{1f600}", "%F0%9F%98%80"),
+        ] {
+            assert_eq!(*expected, build(|form| form.push_encoded(value)));
+        }
+    }
+
+    #[test]
+    fn test_number_len() {
+        for value in &[0, 9, 10, 99, 100, 123_456, u64::MAX] {
+            let mut length = FormLength(0);
+            length.push_number(*value);
+
+            assert_eq!(value.to_string().len(), length.0);
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let buf = build(|form| {
+            form.push_raw("a=");
+            form.push_encoded("b c");
+            form.push_raw("&d=");
+            form.push_number(1234);
+        });
+
+        assert_eq!("a=b%20c&d=1234", buf);
+        assert_eq!(buf.len(), buf.capacity());
+    }
+
+    #[test]
+    fn test_append() {
+        let mut buf = "x".to_owned();
+        super::append(&mut buf, |form| form.push_encoded("a b"));
+
+        assert_eq!("xa%20b", buf);
+    }
+}
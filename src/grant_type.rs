@@ -1,9 +1,23 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{Deserializer, Error as DeError, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+use std::{
+    convert::Infallible,
+    fmt::{Formatter, Result as FmtResult},
+    str::FromStr,
+};
 
 /// Type of approved grant.
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+///
+/// Grant types not known to this version of the crate are kept as
+/// [`Other`] when deserializing or parsing, and serialize back to the same
+/// name, so that persisted or proxied grant types survive new grants being
+/// added.
+///
+/// [`Other`]: #variant.Other
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
-#[serde(rename_all = "snake_case")]
 pub enum GrantType {
     /// Authorization code.
     AuthorizationCode,
@@ -11,9 +25,57 @@ pub enum GrantType {
     ClientCredentials,
     /// Refresh token.
     RefreshToken,
+    /// Grant type not known to this version of the crate, by name.
+    ///
+    /// Known grant types are never parsed into this, so compare names via
+    /// [`name`] rather than constructing this for a known grant type.
+    ///
+    /// [`name`]: #method.name
+    Other(String),
 }
 
 impl GrantType {
+    /// Whether the grant requires interaction from a user.
+    ///
+    /// This is only the case for [`AuthorizationCode`], where a user must
+    /// approve the authorization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::GrantType;
+    ///
+    /// assert!(GrantType::AuthorizationCode.is_user_facing());
+    /// assert!(!GrantType::RefreshToken.is_user_facing());
+    /// ```
+    ///
+    /// [`AuthorizationCode`]: #variant.AuthorizationCode
+    pub fn is_user_facing(&self) -> bool {
+        matches!(self, Self::AuthorizationCode)
+    }
+
+    /// Whether the grant is performed between the application and Discord
+    /// without a user session.
+    ///
+    /// This is only the case for [`ClientCredentials`]. A [`RefreshToken`]
+    /// grant is performed by the application but on behalf of a prior user
+    /// session, so it is neither user facing nor machine to machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::GrantType;
+    ///
+    /// assert!(GrantType::ClientCredentials.is_machine_to_machine());
+    /// assert!(!GrantType::AuthorizationCode.is_machine_to_machine());
+    /// ```
+    ///
+    /// [`ClientCredentials`]: #variant.ClientCredentials
+    /// [`RefreshToken`]: #variant.RefreshToken
+    pub fn is_machine_to_machine(&self) -> bool {
+        matches!(self, Self::ClientCredentials)
+    }
+
     /// Return the name of the grant type.
     ///
     /// This is equivalent to what you would get when serializing it.
@@ -30,23 +92,82 @@ impl GrantType {
             Self::AuthorizationCode => "authorization_code",
             Self::ClientCredentials => "client_credentials",
             Self::RefreshToken => "refresh_token",
+            Self::Other(name) => name,
         }
     }
 }
 
+impl FromStr for GrantType {
+    type Err = Infallible;
+
+    /// Parse a grant type from its name, falling back to [`Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::GrantType;
+    ///
+    /// assert_eq!(Ok(GrantType::RefreshToken), "refresh_token".parse());
+    /// assert_eq!(
+    ///     Ok(GrantType::Other("device_code".to_owned())),
+    ///     "device_code".parse(),
+    /// );
+    /// ```
+    ///
+    /// [`Other`]: #variant.Other
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "authorization_code" => Self::AuthorizationCode,
+            "client_credentials" => Self::ClientCredentials,
+            "refresh_token" => Self::RefreshToken,
+            other => Self::Other(other.to_owned()),
+        })
+    }
+}
+
+struct GrantTypeVisitor;
+
+impl Visitor<'_> for GrantTypeVisitor {
+    type Value = GrantType;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("grant type name")
+    }
+
+    fn visit_str<E: DeError>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(match value.parse() {
+            Ok(grant_type) => grant_type,
+            Err(infallible) => match infallible {},
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for GrantType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(GrantTypeVisitor)
+    }
+}
+
+impl Serialize for GrantType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::GrantType;
     use serde::{Deserialize, Serialize};
+    use serde_test::Token;
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{fmt::Debug, str::FromStr};
 
     assert_impl_all!(
         GrantType: Clone,
-        Copy,
         Debug,
         Deserialize<'static>,
         Eq,
+        FromStr,
         PartialEq,
         Send,
         Serialize,
@@ -58,5 +179,54 @@ mod tests {
         assert_eq!("authorization_code", GrantType::AuthorizationCode.name());
         assert_eq!("client_credentials", GrantType::ClientCredentials.name());
         assert_eq!("refresh_token", GrantType::RefreshToken.name());
+        assert_eq!(
+            "device_code",
+            GrantType::Other("device_code".to_owned()).name()
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        serde_test::assert_tokens(
+            &GrantType::AuthorizationCode,
+            &[Token::Str("authorization_code")],
+        );
+        serde_test::assert_tokens(
+            &GrantType::Other("urn:ietf:params:oauth:grant-type:device_code".to_owned()),
+            &[Token::Str("urn:ietf:params:oauth:grant-type:device_code")],
+        );
+
+        let json = r#"["client_credentials","token_exchange"]"#;
+        let grant_types = serde_json::from_str::<Vec<GrantType>>(json).unwrap();
+        assert_eq!(
+            vec![
+                GrantType::ClientCredentials,
+                GrantType::Other("token_exchange".to_owned()),
+            ],
+            grant_types
+        );
+        assert_eq!(json, serde_json::to_string(&grant_types).unwrap());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Ok(GrantType::AuthorizationCode),
+            "authorization_code".parse()
+        );
+        assert_eq!(
+            Ok(GrantType::Other("Refresh_Token".to_owned())),
+            "Refresh_Token".parse()
+        );
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(GrantType::AuthorizationCode.is_user_facing());
+        assert!(!GrantType::AuthorizationCode.is_machine_to_machine());
+        assert!(!GrantType::ClientCredentials.is_user_facing());
+        assert!(GrantType::ClientCredentials.is_machine_to_machine());
+        assert!(!GrantType::RefreshToken.is_user_facing());
+        assert!(!GrantType::RefreshToken.is_machine_to_machine());
     }
 }
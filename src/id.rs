@@ -0,0 +1,254 @@
+//! IDs of Discord resources.
+//!
+//! With the `model` feature enabled these are the IDs of [`twilight-model`].
+//! Otherwise minimal equivalents are provided, so code constructing IDs via
+//! `ApplicationId(123)`, reading them via `.0`, and formatting them with
+//! [`Display`] works either way.
+//!
+//! [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+//! [`twilight-model`]: https://docs.rs/twilight-model
+
+use serde::de::{Deserializer, Error as DeError, Visitor};
+use std::{
+    fmt::{Formatter, Result as FmtResult},
+    marker::PhantomData,
+};
+
+#[cfg(feature = "model")]
+pub use twilight_model::id::{ApplicationId, GuildId};
+
+#[cfg(not(feature = "model"))]
+pub use self::minimal::{ApplicationId, GuildId};
+
+/// Deserialize an ID from a string or an integer, like `twilight-model`
+/// does.
+struct IdVisitor<T>(PhantomData<T>);
+
+impl<'de, T: From<u64>> Visitor<'de> for IdVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("string or integer snowflake")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_u64<E: DeError>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(T::from(value))
+    }
+
+    fn visit_str<E: DeError>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map(T::from).map_err(DeError::custom)
+    }
+}
+
+/// Deserialize an ID from a string or an integer, regardless of whether the
+/// format calls it a newtype struct.
+///
+/// Formats without types, such as a parsed form, only offer strings, which
+/// the derived implementations of `twilight-model` reject.
+pub(crate) fn deserialize_id<'de, D: Deserializer<'de>, T: From<u64>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserializer.deserialize_any(IdVisitor(PhantomData))
+}
+
+#[cfg(not(feature = "model"))]
+mod minimal {
+    use super::IdVisitor;
+    use serde::{de::Deserializer, Deserialize, Serialize, Serializer};
+    use std::{
+        fmt::{Display, Formatter, Result as FmtResult},
+        marker::PhantomData,
+    };
+
+    /// Serialize an ID as a string, like `twilight-model` does.
+    struct IdString(u64);
+
+    impl Serialize for IdString {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&self.0)
+        }
+    }
+
+    /// ID of an application.
+    ///
+    /// This serializes as a string, like Discord sends IDs, and deserializes
+    /// from either a string or an integer.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub struct ApplicationId(pub u64);
+
+    impl Display for ApplicationId {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            Display::fmt(&self.0, f)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ApplicationId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_newtype_struct("ApplicationId", IdVisitor(PhantomData))
+        }
+    }
+
+    impl From<u64> for ApplicationId {
+        fn from(id: u64) -> Self {
+            Self(id)
+        }
+    }
+
+    impl Serialize for ApplicationId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_newtype_struct("ApplicationId", &IdString(self.0))
+        }
+    }
+
+    /// ID of a guild.
+    ///
+    /// This serializes as a string, like Discord sends IDs, and deserializes
+    /// from either a string or an integer.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub struct GuildId(pub u64);
+
+    impl Display for GuildId {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            Display::fmt(&self.0, f)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for GuildId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_newtype_struct("GuildId", IdVisitor(PhantomData))
+        }
+    }
+
+    impl From<u64> for GuildId {
+        fn from(id: u64) -> Self {
+            Self(id)
+        }
+    }
+
+    impl Serialize for GuildId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_newtype_struct("GuildId", &IdString(self.0))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{ApplicationId, GuildId};
+        use serde::{Deserialize, Serialize};
+        use serde_test::Token;
+        use static_assertions::assert_impl_all;
+        use std::{
+            fmt::{Debug, Display},
+            hash::Hash,
+        };
+
+        assert_impl_all!(
+            ApplicationId: Clone,
+            Copy,
+            Debug,
+            Default,
+            Deserialize<'static>,
+            Display,
+            Eq,
+            From<u64>,
+            Hash,
+            Ord,
+            PartialEq,
+            PartialOrd,
+            Send,
+            Serialize,
+            Sync
+        );
+        assert_impl_all!(
+            GuildId: Clone,
+            Copy,
+            Debug,
+            Default,
+            Deserialize<'static>,
+            Display,
+            Eq,
+            From<u64>,
+            Hash,
+            Ord,
+            PartialEq,
+            PartialOrd,
+            Send,
+            Serialize,
+            Sync
+        );
+
+        #[test]
+        fn test_display() {
+            assert_eq!("123", ApplicationId(123).to_string());
+            assert_eq!("456", GuildId(456).to_string());
+        }
+
+        #[test]
+        fn test_deserialize() {
+            serde_test::assert_de_tokens(
+                &ApplicationId(123),
+                &[
+                    Token::NewtypeStruct {
+                        name: "ApplicationId",
+                    },
+                    Token::Str("123"),
+                ],
+            );
+            serde_test::assert_de_tokens(
+                &GuildId(456),
+                &[Token::NewtypeStruct { name: "GuildId" }, Token::U64(456)],
+            );
+            serde_test::assert_de_tokens_error::<ApplicationId>(
+                &[
+                    Token::NewtypeStruct {
+                        name: "ApplicationId",
+                    },
+                    Token::Str("abc"),
+                ],
+                "invalid digit found in string",
+            );
+        }
+
+        #[test]
+        fn test_serialize() {
+            serde_test::assert_ser_tokens(
+                &ApplicationId(123),
+                &[
+                    Token::NewtypeStruct {
+                        name: "ApplicationId",
+                    },
+                    Token::Str("123"),
+                ],
+            );
+            serde_test::assert_ser_tokens(
+                &GuildId(456),
+                &[Token::NewtypeStruct { name: "GuildId" }, Token::Str("456")],
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApplicationId, GuildId};
+    use serde::de::value::{Error as DeError, StrDeserializer, U64Deserializer};
+
+    #[test]
+    fn test_deserialize_id() {
+        let from_str = super::deserialize_id(StrDeserializer::<DeError>::new("123"));
+        assert_eq!(ApplicationId(123), from_str.unwrap());
+        let from_u64 = super::deserialize_id(U64Deserializer::<DeError>::new(456));
+        assert_eq!(GuildId(456), from_u64.unwrap());
+        assert!(
+            super::deserialize_id::<_, ApplicationId>(StrDeserializer::<DeError>::new("a"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::TokenError;
+use serde::Deserialize;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Maximum number of bytes of the body kept in
+/// [`ResponseParseError::Deserializing`].
+///
+/// [`ResponseParseError::Deserializing`]: enum.ResponseParseError.html#variant.Deserializing
+const BODY_SNIPPET_LENGTH: usize = 256;
+
+/// Replacement of redacted values in the body kept in
+/// [`ResponseParseError::Deserializing`].
+///
+/// [`ResponseParseError::Deserializing`]: enum.ResponseParseError.html#variant.Deserializing
+const REDACTED: &str = "<redacted>";
+
+/// Parsing a response body failed.
+///
+/// This is returned from the `from_json_bytes` functions of response types.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ResponseParseError {
+    /// The body didn't match the response or an OAuth error.
+    Deserializing {
+        /// Start of the body, for debugging.
+        ///
+        /// This is at most 256 bytes long, with invalid UTF-8 replaced. String
+        /// values of keys ending in `token`, such as `access_token`, and of
+        /// `url` keys, which contain the token of webhooks, are replaced with
+        /// `<redacted>`, so the error can be logged.
+        body: String,
+        /// Reason the body didn't match the response.
+        source: serde_json::Error,
+    },
+    /// Discord returned an OAuth error instead of a response.
+    OAuth {
+        /// Error returned by Discord.
+        error: TokenError,
+    },
+}
+
+impl Display for ResponseParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Deserializing { .. } => f.write_str("response body has an unknown shape"),
+            Self::OAuth { error } => {
+                f.write_str("discord returned the oauth error ")?;
+                f.write_str(error.error.name())?;
+
+                if let Some(description) = error.error_description.as_ref() {
+                    f.write_str(": ")?;
+                    f.write_str(description)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ResponseParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Deserializing { source, .. } => Some(source),
+            Self::OAuth { .. } => None,
+        }
+    }
+}
+
+/// Parse a response body, falling back to the OAuth error shape.
+pub(crate) fn from_json_bytes<'a, T: Deserialize<'a>>(
+    bytes: &'a [u8],
+) -> Result<T, ResponseParseError> {
+    serde_json::from_slice(bytes).map_err(|source| {
+        if let Ok(error) = serde_json::from_slice::<TokenError>(bytes) {
+            return ResponseParseError::OAuth { error };
+        }
+
+        let mut body = redact(&String::from_utf8_lossy(bytes));
+        let mut length = body.len().min(BODY_SNIPPET_LENGTH);
+
+        while !body.is_char_boundary(length) {
+            length -= 1;
+        }
+
+        body.truncate(length);
+
+        ResponseParseError::Deserializing { body, source }
+    })
+}
+
+/// Replace the string values of secret keys in a JSON body.
+///
+/// This works on malformed and truncated bodies: any string literal that is
+/// followed by a colon is a key.
+fn redact(body: &str) -> String {
+    let mut redacted = String::with_capacity(body.len());
+    let mut redact_next = false;
+    let mut rest = body;
+
+    while let Some(start) = rest.find('"') {
+        redacted.push_str(&rest[..start]);
+        let literal = &rest[start + 1..];
+
+        let Some(end) = string_end(literal) else {
+            redacted.push('"');
+            redacted.push_str(if redact_next { REDACTED } else { literal });
+
+            return redacted;
+        };
+
+        let (content, tail) = (&literal[..end], &literal[end + 1..]);
+        redacted.push('"');
+
+        if redact_next {
+            redacted.push_str(REDACTED);
+            redact_next = false;
+        } else {
+            redacted.push_str(content);
+
+            let value = tail.trim_start().strip_prefix(':').map(str::trim_start);
+            redact_next =
+                is_secret_key(content) && value.is_some_and(|value| value.starts_with('"'));
+        }
+
+        redacted.push('"');
+        rest = tail;
+    }
+
+    redacted.push_str(rest);
+
+    redacted
+}
+
+/// Return the index of the closing quote of a string literal, without its
+/// opening quote.
+fn string_end(literal: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (idx, byte) in literal.bytes().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'"' => return Some(idx),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Whether a key's value is a secret.
+fn is_secret_key(key: &str) -> bool {
+    key.ends_with("token") || key == "url"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResponseParseError, BODY_SNIPPET_LENGTH};
+    use crate::{
+        fixtures,
+        request::{
+            access_token_exchange::{AccessTokenExchangeResponse, AccessTokenExchangeResponseRef},
+            client_credentials_grant::ClientCredentialsGrantResponse,
+            refresh_token_exchange::{
+                RefreshTokenExchangeResponse, RefreshTokenExchangeResponseRef,
+            },
+        },
+        TokenErrorKind,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        borrow::Cow,
+        error::Error,
+        fmt::{Debug, Display},
+    };
+
+    assert_fields!(ResponseParseError::Deserializing: body, source);
+    assert_fields!(ResponseParseError::OAuth: error);
+    assert_impl_all!(ResponseParseError: Debug, Display, Error, Send, Sync);
+
+    #[test]
+    fn test_success() {
+        assert_eq!(
+            fixtures::access_token_response(),
+            AccessTokenExchangeResponse::from_json_bytes(fixtures::ACCESS_TOKEN_RESPONSE).unwrap()
+        );
+        assert_eq!(
+            fixtures::client_credentials_grant_response(),
+            ClientCredentialsGrantResponse::from_json_bytes(
+                fixtures::CLIENT_CREDENTIALS_GRANT_RESPONSE
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            fixtures::refresh_token_response(),
+            RefreshTokenExchangeResponse::from_json_bytes(fixtures::REFRESH_TOKEN_RESPONSE)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_success_borrowed() {
+        let response =
+            AccessTokenExchangeResponseRef::from_json_bytes(fixtures::ACCESS_TOKEN_RESPONSE)
+                .unwrap();
+        assert!(matches!(response.access_token, Cow::Borrowed(_)));
+        assert_eq!(fixtures::access_token_response(), response.into_owned());
+    }
+
+    #[test]
+    fn test_escaped_borrowed() {
+        let body = br#"{
+            "access_token": "a\/b",
+            "expires_in": 604800,
+            "refresh_token": "c",
+            "scope": "identify\u0020email",
+            "token_type": "Bearer"
+        }"#;
+        let response = RefreshTokenExchangeResponseRef::from_json_bytes(body).unwrap();
+
+        assert!(matches!(response.access_token, Cow::Owned(_)));
+        assert_eq!("a/b", response.access_token);
+        assert!(matches!(response.refresh_token, Cow::Borrowed("c")));
+        assert!(matches!(response.scope, Cow::Owned(_)));
+        assert_eq!("identify email", response.scope);
+    }
+
+    #[test]
+    fn test_invalid_grant_borrowed() {
+        let body = br#"{"error": "invalid_grant"}"#;
+
+        assert!(matches!(
+            AccessTokenExchangeResponseRef::from_json_bytes(body),
+            Err(ResponseParseError::OAuth { .. })
+        ));
+    }
+
+    #[cfg(feature = "model")]
+    #[test]
+    fn test_success_webhook() {
+        use crate::request::webhook_token_exchange::WebhookTokenExchangeResponse;
+
+        assert_eq!(
+            fixtures::webhook_token_response(),
+            WebhookTokenExchangeResponse::from_json_bytes(fixtures::WEBHOOK_TOKEN_RESPONSE)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_grant() {
+        let body =
+            br#"{"error": "invalid_grant", "error_description": "Invalid \"code\" in request."}"#;
+        let error = RefreshTokenExchangeResponse::from_json_bytes(body).unwrap_err();
+
+        match &error {
+            ResponseParseError::OAuth { error } => {
+                assert_eq!(TokenErrorKind::InvalidGrant, error.error);
+                assert_eq!(
+                    Some("Invalid \"code\" in request."),
+                    error.error_description.as_deref()
+                );
+            }
+            _ => panic!("expected an oauth error"),
+        }
+
+        assert!(error.source().is_none());
+        assert_eq!(
+            "discord returned the oauth error invalid_grant: Invalid \"code\" in request.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_garbage() {
+        let error =
+            AccessTokenExchangeResponse::from_json_bytes(b"<html>bad gateway</html>").unwrap_err();
+
+        match &error {
+            ResponseParseError::Deserializing { body, .. } => {
+                assert_eq!("<html>bad gateway</html>", body);
+            }
+            _ => panic!("expected a deserializing error"),
+        }
+
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_deserializing_redacted() {
+        let body = br#"{
+            "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            "expires_in": 604800,
+            "refresh_token":"D43f5y0a\"hjqew82jZ4NViEr2YafMKhue",
+            "scope": "identify",
+            "token_type": "MAC",
+            "webhook": {"token": "kKDdjXa1g9tKNs0", "url": "https://discord.com/api/webhooks/1/kKDdjXa1g9tKNs0"}
+        }"#;
+        let error = AccessTokenExchangeResponse::from_json_bytes(body).unwrap_err();
+
+        match error {
+            ResponseParseError::Deserializing { body, .. } => {
+                assert!(body.contains(r#""access_token": "<redacted>""#));
+                assert!(body.contains(r#""refresh_token":"<redacted>""#));
+                assert!(body.contains(r#""expires_in": 604800"#));
+                assert!(body.contains(r#""token_type": "MAC""#));
+                assert!(!body.contains("6qrZcUqja7812RVdnEKjpzOL4CvHBFG"));
+                assert!(!body.contains("hjqew82jZ4NViEr2YafMKhue"));
+                assert!(!body.contains("kKDdjXa1g9tKNs0"));
+            }
+            _ => panic!("expected a deserializing error"),
+        }
+    }
+
+    #[test]
+    fn test_redact_truncated() {
+        assert_eq!(
+            r#"{"expires_in": 1, "access_token": "<redacted>"#,
+            super::redact(r#"{"expires_in": 1, "access_token": "6qrZcUq"#)
+        );
+    }
+
+    #[test]
+    fn test_garbage_truncated() {
+        let body = vec![b'x'; BODY_SNIPPET_LENGTH * 2];
+        let error = AccessTokenExchangeResponse::from_json_bytes(&body).unwrap_err();
+
+        match error {
+            ResponseParseError::Deserializing { body, .. } => {
+                assert_eq!(BODY_SNIPPET_LENGTH, body.len());
+            }
+            _ => panic!("expected a deserializing error"),
+        }
+    }
+}
@@ -13,6 +13,64 @@
 //! twilight-oauth2 = { branch = "trunk", git = "https://github.com/twilight-rs/twilight" }
 //! ```
 //!
+//! ## Features
+//!
+//! ### `fs`
+//!
+//! The `fs` feature enables the [`JsonFileTokenStore`], which persists tokens
+//! in a JSON file. This is disabled by default.
+//!
+//! ### `json`
+//!
+//! The `json` feature adds `from_json_bytes` functions to response types,
+//! which parse a response body and return a [`ResponseParseError`] telling
+//! OAuth errors returned by Discord apart from unknown bodies. This is
+//! disabled by default.
+//!
+//! ### `loopback`
+//!
+//! The `loopback` feature enables the [`loopback`] module, which captures the
+//! authorization redirect on a local port for CLI and desktop applications.
+//! This is disabled by default.
+//!
+//! ### `model`
+//!
+//! The `model` feature uses the ID and permission types of
+//! [`twilight-model`] and enables exchanging codes for webhooks and adding
+//! bots via the [`bot_authorization`] flow. Without it, minimal [`id`] types
+//! are provided and bot permissions are set as raw bits. This is enabled by
+//! default.
+//!
+//! ### `test-util`
+//!
+//! The `test-util` feature exposes the [`fixtures`] module, containing
+//! realistic response fixtures and a manually advanced clock for testing
+//! code that uses this crate. This is disabled by default.
+//!
+//! ### `tracing`
+//!
+//! The `tracing` feature emits [`tracing`] spans when token requests are
+//! built, named after the grant (such as `oauth2.exchange_code`), with the
+//! application ID, grant type, and scopes as fields. The built request body is
+//! logged through its redacted `Display` implementation, so client secrets,
+//! authorization codes, and tokens are never recorded. This is disabled by
+//! default.
+//!
+//! ### `zeroize`
+//!
+//! The `zeroize` feature zeroes the memory of [`AccessToken`]s and
+//! [`RefreshToken`]s when they are dropped. This is disabled by default.
+//!
+//! [`AccessToken`]: struct.AccessToken.html
+//! [`JsonFileTokenStore`]: token_store/struct.JsonFileTokenStore.html
+//! [`RefreshToken`]: struct.RefreshToken.html
+//! [`ResponseParseError`]: enum.ResponseParseError.html
+//! [`bot_authorization`]: bot_authorization/index.html
+//! [`fixtures`]: fixtures/index.html
+//! [`id`]: id/index.html
+//! [`loopback`]: loopback/index.html
+//! [`tracing`]: https://docs.rs/tracing
+//! [`twilight-model`]: https://docs.rs/twilight-model
 //! [`twilight-rs`]: https://github.com/twilight-rs/twilight
 //! [OAuth 2 API]: https://discord.com/developers/docs/topics/oauth2
 
@@ -28,18 +86,54 @@
 #![allow(
     clippy::doc_markdown,
     clippy::module_name_repetitions,
-    clippy::must_use_candidate
+    clippy::must_use_candidate,
+    clippy::struct_field_names
 )]
 
 pub mod authorization_url;
+#[cfg(feature = "model")]
+pub mod bot_authorization;
 pub mod client;
+pub mod client_pool;
+#[cfg(any(feature = "test-util", test))]
+pub mod fixtures;
+pub mod id;
+#[cfg(feature = "loopback")]
+pub mod loopback;
 pub mod request;
+pub mod scope;
+pub mod session_store;
+pub mod token_store;
 
+mod base64;
+mod clock;
+mod form;
 mod grant_type;
+#[cfg(feature = "json")]
+mod json;
 mod prompt;
-mod scope;
+mod rate_limit_info;
+mod refresh_policy;
+mod stored_token;
+mod token;
+mod token_error;
+mod token_response;
 mod token_type;
 
 pub use self::{
-    client::Client, grant_type::GrantType, prompt::Prompt, scope::Scope, token_type::TokenType,
+    client::Client,
+    clock::{Clock, SystemClock},
+    grant_type::GrantType,
+    prompt::Prompt,
+    rate_limit_info::RateLimitInfo,
+    refresh_policy::RefreshPolicy,
+    scope::Scope,
+    stored_token::{RefreshRequestError, StoredToken},
+    token::{AccessToken, RefreshToken},
+    token_error::{TokenError, TokenErrorKind},
+    token_response::TokenResponse,
+    token_type::TokenType,
 };
+
+#[cfg(feature = "json")]
+pub use self::json::ResponseParseError;
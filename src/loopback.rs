@@ -0,0 +1,715 @@
+//! Capture the authorization redirect on a loopback address.
+//!
+//! CLI and desktop applications can't receive redirects on a public URL, so
+//! they instead listen on a local port and register a redirect URI pointing
+//! at it. [`LoopbackRedirect`] binds such a port, waits for the request the
+//! user's browser makes after authorization, responds with a small HTML page
+//! telling the user whether authorization succeeded, and returns the code and
+//! state from the query.
+//!
+//! Waiting for the callback blocks the current thread. In asynchronous code
+//! run it on a thread meant for blocking work.
+//!
+//! This is only available with the `loopback` feature enabled.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//! use twilight_oauth2::{id::ApplicationId, loopback::LoopbackRedirect, Client, Scope};
+//!
+//! let redirect = LoopbackRedirect::bind("127.0.0.1:8080")?;
+//! let redirect_uri = redirect.redirect_uri().to_owned();
+//! let client = Client::new(ApplicationId(123), "secret", &[&redirect_uri])
+//!     .expect("redirect uri is valid");
+//!
+//! let url = client
+//!     .authorization_url(&redirect_uri)
+//!     .expect("redirect uri is configured")
+//!     .with_scopes([Scope::Identify])
+//!     .with_state("random state")
+//!     .build();
+//! println!("open this url in your browser: {}", url);
+//!
+//! let callback = redirect.wait_for_callback(Duration::from_secs(120))?;
+//! assert_eq!(Some("random state"), callback.state.as_deref());
+//! # Ok(()) }
+//! ```
+//!
+//! [`LoopbackRedirect`]: struct.LoopbackRedirect.html
+
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{Error as IoError, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+use url::{form_urlencoded, Url};
+
+/// Maximum length of the request head read from the browser.
+const MAX_REQUEST_LENGTH: usize = 8 * 1024;
+
+/// How long to sleep between checks for an incoming connection.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Maximum time to wait for the request line of a single connection.
+///
+/// Browsers may open connections they never send a request on, which must not
+/// hold up the redirect arriving on another connection.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default page shown to the user after the redirect was captured.
+const DEFAULT_RESPONSE_BODY: &str = "<!DOCTYPE html>\
+    <html><head><meta charset=\"utf-8\"><title>Authorization complete</title></head>\
+    <body><p>Authorization complete. You can close this window.</p></body></html>";
+
+/// Default page shown to the user after a redirect without a code was
+/// captured, such as when they denied authorization.
+const DEFAULT_FAILURE_BODY: &str = "<!DOCTYPE html>\
+    <html><head><meta charset=\"utf-8\"><title>Authorization failed</title></head>\
+    <body><p>Authorization failed. You can close this window.</p></body></html>";
+
+/// Waiting for the loopback redirect failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoopbackError {
+    /// The user denied authorization, or Discord returned another error.
+    Denied {
+        /// Error code, such as `access_denied`.
+        error: String,
+        /// Human readable description of the error.
+        error_description: Option<String>,
+        /// State that was sent in the authorization URL.
+        state: Option<String>,
+    },
+    /// Binding the listener or communicating with the browser failed.
+    Io {
+        /// Reason for the error.
+        source: IoError,
+    },
+    /// The request isn't an HTTP request with a request target, or the
+    /// redirect URL isn't a valid URL.
+    MalformedRequest,
+    /// The redirect has neither a code nor an error.
+    MissingCode,
+    /// No redirect was received before the timeout elapsed.
+    TimedOut,
+}
+
+impl Display for LoopbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Denied { error, .. } => {
+                f.write_str("authorization was denied: ")?;
+
+                f.write_str(error)
+            }
+            Self::Io { .. } => f.write_str("communicating over the loopback address failed"),
+            Self::MalformedRequest => f.write_str("the redirect request is malformed"),
+            Self::MissingCode => f.write_str("the redirect has no code"),
+            Self::TimedOut => f.write_str("timed out waiting for the redirect"),
+        }
+    }
+}
+
+impl Error for LoopbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source } => Some(source),
+            Self::Denied { .. } | Self::MalformedRequest | Self::MissingCode | Self::TimedOut => {
+                None
+            }
+        }
+    }
+}
+
+impl From<IoError> for LoopbackError {
+    fn from(source: IoError) -> Self {
+        Self::Io { source }
+    }
+}
+
+/// Query of a successful authorization redirect.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorizationCallback {
+    /// Authorization code to exchange for an access token.
+    pub code: String,
+    /// State that was sent in the authorization URL.
+    ///
+    /// Compare this to the state you sent before exchanging the code.
+    pub state: Option<String>,
+}
+
+impl AuthorizationCallback {
+    /// Parse the query of an authorization redirect.
+    ///
+    /// This is useful for servers receiving the redirect themselves. A
+    /// leading `?` is ignored, and a full redirect URL, such as
+    /// `myapp://callback?code=x&state=y`, is parsed via
+    /// [`from_redirect_url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoopbackError::Denied`] if the query contains an error, such
+    /// as when the user denied authorization.
+    ///
+    /// Returns [`LoopbackError::MissingCode`] if the query contains neither a
+    /// code nor an error.
+    ///
+    /// [`LoopbackError::Denied`]: enum.LoopbackError.html#variant.Denied
+    /// [`LoopbackError::MissingCode`]: enum.LoopbackError.html#variant.MissingCode
+    /// [`from_redirect_url`]: #method.from_redirect_url
+    pub fn from_query(query: &str) -> Result<Self, LoopbackError> {
+        match Url::parse(query) {
+            Ok(url) => Self::from_url(&url),
+            Err(_) => Self::parse_query(query.strip_prefix('?').unwrap_or(query)),
+        }
+    }
+
+    /// Parse the query of an authorization redirect into a callback.
+    fn parse_query(query: &str) -> Result<Self, LoopbackError> {
+        let mut code = None;
+        let mut error = None;
+        let mut error_description = None;
+        let mut state = None;
+
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let slot = match key.as_ref() {
+                "code" => &mut code,
+                "error" => &mut error,
+                "error_description" => &mut error_description,
+                "state" => &mut state,
+                _ => continue,
+            };
+
+            slot.replace(value.into_owned());
+        }
+
+        if let Some(error) = error {
+            return Err(LoopbackError::Denied {
+                error,
+                error_description,
+                state,
+            });
+        }
+
+        match code {
+            Some(code) => Ok(Self { code, state }),
+            None => Err(LoopbackError::MissingCode),
+        }
+    }
+
+    /// Parse the URL an authorization redirect was made to.
+    ///
+    /// Any scheme is accepted, including custom schemes of mobile and desktop
+    /// applications such as `myapp://callback?code=x&state=y`. The fragment
+    /// is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoopbackError::Denied`] if the query contains an error, such
+    /// as when the user denied authorization.
+    ///
+    /// Returns [`LoopbackError::MalformedRequest`] if the URL isn't a valid
+    /// absolute URL.
+    ///
+    /// Returns [`LoopbackError::MissingCode`] if the query contains neither a
+    /// code nor an error.
+    ///
+    /// [`LoopbackError::Denied`]: enum.LoopbackError.html#variant.Denied
+    /// [`LoopbackError::MalformedRequest`]: enum.LoopbackError.html#variant.MalformedRequest
+    /// [`LoopbackError::MissingCode`]: enum.LoopbackError.html#variant.MissingCode
+    pub fn from_redirect_url(url: &str) -> Result<Self, LoopbackError> {
+        let url = Url::parse(url).map_err(|_| LoopbackError::MalformedRequest)?;
+
+        Self::from_url(&url)
+    }
+
+    fn from_url(url: &Url) -> Result<Self, LoopbackError> {
+        Self::parse_query(url.query().unwrap_or_default())
+    }
+}
+
+/// Listener capturing a single authorization redirect on a loopback address.
+///
+/// Refer to the [module documentation] for an example.
+///
+/// [module documentation]: index.html
+#[derive(Debug)]
+pub struct LoopbackRedirect {
+    failure_body: Cow<'static, str>,
+    listener: TcpListener,
+    redirect_uri: String,
+    response_body: Cow<'static, str>,
+}
+
+impl LoopbackRedirect {
+    /// Bind a listener to an address.
+    ///
+    /// Bind to port 0 to let the operating system pick a free port, and read
+    /// the result from [`redirect_uri`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoopbackError::Io`] if binding the listener failed.
+    ///
+    /// [`LoopbackError::Io`]: enum.LoopbackError.html#variant.Io
+    /// [`redirect_uri`]: #method.redirect_uri
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, LoopbackError> {
+        let listener = TcpListener::bind(address)?;
+        let redirect_uri = format!("http://{}/", listener.local_addr()?);
+
+        Ok(Self {
+            failure_body: Cow::Borrowed(DEFAULT_FAILURE_BODY),
+            listener,
+            redirect_uri,
+            response_body: Cow::Borrowed(DEFAULT_RESPONSE_BODY),
+        })
+    }
+
+    /// Return the redirect URI of the bound address, such as
+    /// `http://127.0.0.1:8080/`.
+    ///
+    /// This must be one of the client's configured redirect URIs.
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Set the HTML page shown to the user once a redirect without a code was
+    /// captured, such as when they denied authorization.
+    ///
+    /// Defaults to a page telling the user that authorization failed and that
+    /// they can close the window.
+    pub fn failure_body(&mut self, html: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.failure_body = html.into();
+
+        self
+    }
+
+    /// Set the HTML page shown to the user once the redirect was captured.
+    ///
+    /// Defaults to a page telling the user that they can close the window.
+    pub fn response_body(&mut self, html: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.response_body = html.into();
+
+        self
+    }
+
+    /// Wait for the browser to make the redirect request.
+    ///
+    /// Connections are accepted until one makes a request whose query has a
+    /// code or an error, after which the listener is closed. A redirect with a
+    /// code is answered with the [response body], and one with an error with
+    /// a `400 Bad Request` and the [failure body]. Other requests, such as for
+    /// `/favicon.ico`, are answered with a `404 Not Found`, and connections
+    /// that don't make an HTTP request are closed.
+    ///
+    /// A timeout too large to be represented waits indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoopbackError::Denied`] if the redirect contains an error,
+    /// such as when the user denied authorization.
+    ///
+    /// Returns [`LoopbackError::Io`] if accepting connections or answering the
+    /// redirect request failed.
+    ///
+    /// Returns [`LoopbackError::TimedOut`] if no redirect was received within
+    /// the timeout.
+    ///
+    /// [`LoopbackError::Denied`]: enum.LoopbackError.html#variant.Denied
+    /// [`LoopbackError::Io`]: enum.LoopbackError.html#variant.Io
+    /// [`LoopbackError::TimedOut`]: enum.LoopbackError.html#variant.TimedOut
+    /// [failure body]: #method.failure_body
+    /// [response body]: #method.response_body
+    pub fn wait_for_callback(
+        self,
+        timeout: Duration,
+    ) -> Result<AuthorizationCallback, LoopbackError> {
+        let deadline = Instant::now().checked_add(timeout);
+
+        loop {
+            let mut stream = self.accept(deadline)?;
+
+            let target = match read_request_target(&mut stream, deadline) {
+                Ok(target) => target,
+                Err(LoopbackError::MalformedRequest) => {
+                    let _result = respond(&mut stream, "400 Bad Request", "");
+
+                    continue;
+                }
+                Err(_) => continue,
+            };
+
+            if !is_callback(&target) {
+                let _result = respond(&mut stream, "404 Not Found", "");
+
+                continue;
+            }
+
+            let result = parse_callback(&target);
+            let (status, body) = if result.is_ok() {
+                ("200 OK", &self.response_body)
+            } else {
+                ("400 Bad Request", &self.failure_body)
+            };
+            respond(&mut stream, status, body)?;
+
+            return result;
+        }
+    }
+
+    /// Accept a connection, polling until the deadline.
+    ///
+    /// Without a deadline this polls indefinitely.
+    fn accept(&self, deadline: Option<Instant>) -> Result<TcpStream, LoopbackError> {
+        self.listener.set_nonblocking(true)?;
+
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+
+                    return Ok(stream);
+                }
+                Err(source) if source.kind() == ErrorKind::WouldBlock => {
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(LoopbackError::TimedOut);
+                    }
+
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Err(source) => return Err(LoopbackError::Io { source }),
+            }
+        }
+    }
+}
+
+/// Write a response with an HTML body and close the connection.
+fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<(), IoError> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: text/html; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\r\n",
+        body.len(),
+    )?;
+    stream.write_all(body.as_bytes())?;
+
+    stream.flush()
+}
+
+/// Read the request line, returning its target.
+///
+/// Reading is limited to the [`READ_TIMEOUT`] and doesn't extend past the
+/// deadline.
+///
+/// [`READ_TIMEOUT`]: constant.READ_TIMEOUT.html
+fn read_request_target(
+    stream: &mut TcpStream,
+    deadline: Option<Instant>,
+) -> Result<String, LoopbackError> {
+    let timeout = deadline.map_or(READ_TIMEOUT, |deadline| {
+        deadline
+            .saturating_duration_since(Instant::now())
+            .clamp(POLL_INTERVAL, READ_TIMEOUT)
+    });
+    stream.set_read_timeout(Some(timeout))?;
+
+    let mut buf = Vec::new();
+    let mut chunk = [0; 1024];
+
+    let line_end = loop {
+        if let Some(position) = buf.windows(2).position(|window| window == b"\r\n") {
+            break position;
+        }
+
+        if buf.len() >= MAX_REQUEST_LENGTH {
+            return Err(LoopbackError::MalformedRequest);
+        }
+
+        let read = match stream.read(&mut chunk) {
+            Ok(read) => read,
+            Err(source)
+                if source.kind() == ErrorKind::WouldBlock
+                    || source.kind() == ErrorKind::TimedOut =>
+            {
+                return Err(LoopbackError::TimedOut)
+            }
+            Err(source) => return Err(LoopbackError::Io { source }),
+        };
+
+        if read == 0 {
+            return Err(LoopbackError::MalformedRequest);
+        }
+
+        buf.extend_from_slice(&chunk[..read]);
+    };
+
+    let line =
+        std::str::from_utf8(&buf[..line_end]).map_err(|_| LoopbackError::MalformedRequest)?;
+    let mut parts = line.split(' ');
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(_), Some(target), Some(version)) if version.starts_with("HTTP/") => {
+            Ok(target.to_owned())
+        }
+        _ => Err(LoopbackError::MalformedRequest),
+    }
+}
+
+/// Whether the query of a request target has a code or an error, making it
+/// the authorization redirect.
+fn is_callback(target: &str) -> bool {
+    let query = target.split_once('?').map_or("", |(_, query)| query);
+
+    form_urlencoded::parse(query.as_bytes()).any(|(key, _)| key == "code" || key == "error")
+}
+
+/// Parse the query of a request target into a callback.
+fn parse_callback(target: &str) -> Result<AuthorizationCallback, LoopbackError> {
+    let query = target.split_once('?').map_or("", |(_, query)| query);
+
+    AuthorizationCallback::parse_query(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorizationCallback, LoopbackError, LoopbackRedirect};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    assert_fields!(AuthorizationCallback: code, state);
+    assert_fields!(LoopbackError::Denied: error, error_description, state);
+    assert_fields!(LoopbackError::Io: source);
+    assert_impl_all!(AuthorizationCallback: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(LoopbackError: Debug, Display, Error, Send, Sync);
+    assert_impl_all!(LoopbackRedirect: Debug, Send, Sync);
+
+    /// Return the socket address of a redirect.
+    fn address(redirect: &LoopbackRedirect) -> String {
+        redirect
+            .redirect_uri()
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_owned()
+    }
+
+    /// Send a request with the target to an address, returning the response.
+    fn send(address: &str, target: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        response
+    }
+
+    /// Bind a redirect and send a request with the target to it, returning the
+    /// result and the response.
+    fn request(
+        redirect: LoopbackRedirect,
+        target: &str,
+    ) -> (Result<AuthorizationCallback, LoopbackError>, String) {
+        let address = address(&redirect);
+        let handle = thread::spawn(move || redirect.wait_for_callback(Duration::from_secs(5)));
+        let response = send(&address, target);
+
+        (handle.join().unwrap(), response)
+    }
+
+    #[test]
+    fn test_callback() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        assert!(redirect.redirect_uri().starts_with("http://127.0.0.1:"));
+        assert!(redirect.redirect_uri().ends_with('/'));
+
+        let (result, response) = request(redirect, "/?code=abc%2B123&state=a%20b");
+
+        assert_eq!(
+            AuthorizationCallback {
+                code: "abc+123".to_owned(),
+                state: Some("a b".to_owned()),
+            },
+            result.unwrap()
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("You can close this window.</p></body></html>"));
+    }
+
+    #[test]
+    fn test_response_body() {
+        let mut redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        redirect.response_body("<p>done</p>");
+
+        let (result, response) = request(redirect, "/?code=abc");
+
+        assert_eq!("abc", result.unwrap().code);
+        assert!(response.contains("Content-Length: 11\r\n"));
+        assert!(response.ends_with("\r\n\r\n<p>done</p>"));
+    }
+
+    #[test]
+    fn test_denied() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        let (result, response) = request(
+            redirect,
+            "/?error=access_denied&error_description=The+resource+owner+denied&state=xyz",
+        );
+
+        match result {
+            Err(LoopbackError::Denied {
+                error,
+                error_description,
+                state,
+            }) => {
+                assert_eq!("access_denied", error);
+                assert_eq!(
+                    Some("The resource owner denied"),
+                    error_description.as_deref()
+                );
+                assert_eq!(Some("xyz"), state.as_deref());
+            }
+            _ => panic!("expected a denied error"),
+        }
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+        assert!(response.contains("Authorization failed."));
+        assert!(!response.contains("Authorization complete."));
+    }
+
+    #[test]
+    fn test_failure_body() {
+        let mut redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        redirect
+            .failure_body("<p>denied</p>")
+            .response_body("<p>done</p>");
+
+        let (result, response) = request(redirect, "/?error=access_denied");
+
+        assert!(matches!(result, Err(LoopbackError::Denied { .. })));
+        assert!(response.contains("Content-Length: 13\r\n"));
+        assert!(response.ends_with("\r\n\r\n<p>denied</p>"));
+    }
+
+    #[test]
+    fn test_not_found() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        let address = address(&redirect);
+        let handle = thread::spawn(move || redirect.wait_for_callback(Duration::from_secs(5)));
+
+        let response = send(&address, "/favicon.ico");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        let response = send(&address, "/?state=xyz");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = send(&address, "/?code=abc");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert_eq!("abc", handle.join().unwrap().unwrap().code);
+    }
+
+    #[test]
+    fn test_ignores_malformed() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        let address = address(&redirect);
+        let handle = thread::spawn(move || redirect.wait_for_callback(Duration::from_secs(5)));
+
+        drop(TcpStream::connect(&address).unwrap());
+
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream.write_all(b"not http\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+
+        send(&address, "/?code=abc");
+        assert_eq!("abc", handle.join().unwrap().unwrap().code);
+    }
+
+    #[test]
+    fn test_unrepresentable_timeout() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+        let address = address(&redirect);
+        let handle = thread::spawn(move || redirect.wait_for_callback(Duration::MAX));
+
+        send(&address, "/?code=abc");
+        assert_eq!("abc", handle.join().unwrap().unwrap().code);
+    }
+
+    #[test]
+    fn test_from_query() {
+        let expected = AuthorizationCallback {
+            code: "abc+123".to_owned(),
+            state: Some("a b".to_owned()),
+        };
+
+        assert_eq!(
+            expected,
+            AuthorizationCallback::from_query("code=abc%2B123&state=a+b").unwrap()
+        );
+        assert_eq!(
+            expected,
+            AuthorizationCallback::from_query("?state=a%20b&code=abc%2B123").unwrap()
+        );
+        assert!(matches!(
+            AuthorizationCallback::from_query("error=access_denied"),
+            Err(LoopbackError::Denied { .. })
+        ));
+        assert!(matches!(
+            AuthorizationCallback::from_query(""),
+            Err(LoopbackError::MissingCode)
+        ));
+    }
+
+    #[test]
+    fn test_from_redirect_url() {
+        let expected = AuthorizationCallback {
+            code: "x".to_owned(),
+            state: Some("y".to_owned()),
+        };
+
+        for url in &[
+            "myapp://callback?code=x&state=y",
+            "MyApp://callback?code=x&state=y",
+            "myapp:callback?code=x&state=y#fragment",
+            "com.example.app:/oauth?state=y&code=x",
+        ] {
+            assert_eq!(
+                expected,
+                AuthorizationCallback::from_redirect_url(url).unwrap()
+            );
+            assert_eq!(expected, AuthorizationCallback::from_query(url).unwrap());
+        }
+
+        assert!(matches!(
+            AuthorizationCallback::from_redirect_url("code=x&state=y"),
+            Err(LoopbackError::MalformedRequest)
+        ));
+        assert!(matches!(
+            AuthorizationCallback::from_redirect_url("myapp://callback"),
+            Err(LoopbackError::MissingCode)
+        ));
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let redirect = LoopbackRedirect::bind("127.0.0.1:0").unwrap();
+
+        assert!(matches!(
+            redirect.wait_for_callback(Duration::from_millis(50)),
+            Err(LoopbackError::TimedOut)
+        ));
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Ratelimit information parsed from the headers of a token endpoint
+/// response.
+///
+/// Every field is optional: a missing header or a header with a malformed
+/// value results in the field being `None` rather than the whole parse
+/// failing.
+///
+/// Refer to [Discord's documentation] for more information about each
+/// header.
+///
+/// [Discord's documentation]: https://discord.com/developers/docs/topics/rate-limits#header-format
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RateLimitInfo {
+    /// Unique string denoting the ratelimit bucket the request belongs to.
+    ///
+    /// Parsed from the `X-RateLimit-Bucket` header.
+    pub bucket: Option<String>,
+    /// Number of requests that can be made in the current window.
+    ///
+    /// Parsed from the `X-RateLimit-Limit` header.
+    pub limit: Option<u64>,
+    /// Number of requests remaining in the current window.
+    ///
+    /// Parsed from the `X-RateLimit-Remaining` header.
+    pub remaining: Option<u64>,
+    /// Time at which the ratelimit window resets.
+    ///
+    /// Parsed from the `X-RateLimit-Reset` header.
+    pub reset: Option<SystemTime>,
+    /// Amount of time until the ratelimit window resets.
+    ///
+    /// Parsed from the `X-RateLimit-Reset-After` header.
+    pub reset_after: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    const BUCKET: &'static str = "x-ratelimit-bucket";
+    const LIMIT: &'static str = "x-ratelimit-limit";
+    const REMAINING: &'static str = "x-ratelimit-remaining";
+    const RESET: &'static str = "x-ratelimit-reset";
+    const RESET_AFTER: &'static str = "x-ratelimit-reset-after";
+
+    /// Parse ratelimit information from a list of response headers.
+    ///
+    /// Header names are matched case-insensitively. Headers unrelated to
+    /// ratelimiting are ignored.
+    ///
+    /// Returns `None` if none of the ratelimit headers are present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use twilight_oauth2::RateLimitInfo;
+    ///
+    /// let headers = [
+    ///     ("Content-Type", "application/json"),
+    ///     ("X-RateLimit-Limit", "5"),
+    ///     ("X-RateLimit-Remaining", "4"),
+    ///     ("X-RateLimit-Reset-After", "1.5"),
+    /// ];
+    ///
+    /// let info = RateLimitInfo::from_headers(headers.iter().copied()).unwrap();
+    /// assert_eq!(Some(5), info.limit);
+    /// assert_eq!(Some(4), info.remaining);
+    /// assert_eq!(Some(Duration::from_millis(1500)), info.reset_after);
+    /// ```
+    pub fn from_headers<'a>(headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Option<Self> {
+        let mut info = Self::default();
+        let mut found = false;
+
+        for (name, value) in headers {
+            let value = value.trim();
+
+            if name.eq_ignore_ascii_case(Self::BUCKET) {
+                info.bucket = Some(value.to_owned());
+            } else if name.eq_ignore_ascii_case(Self::LIMIT) {
+                info.limit = value.parse().ok();
+            } else if name.eq_ignore_ascii_case(Self::REMAINING) {
+                info.remaining = value.parse().ok();
+            } else if name.eq_ignore_ascii_case(Self::RESET) {
+                info.reset = parse_seconds(value).and_then(|reset| UNIX_EPOCH.checked_add(reset));
+            } else if name.eq_ignore_ascii_case(Self::RESET_AFTER) {
+                info.reset_after = parse_seconds(value);
+            } else {
+                continue;
+            }
+
+            found = true;
+        }
+
+        found.then_some(info)
+    }
+}
+
+/// Parse a number of seconds, possibly with a fractional part.
+fn parse_seconds(value: &str) -> Option<Duration> {
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimitInfo;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::Debug,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    assert_fields!(RateLimitInfo: bucket, limit, remaining, reset, reset_after);
+    assert_impl_all!(RateLimitInfo: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_full_headers() {
+        let headers = [
+            ("X-RateLimit-Bucket", "abcd1234"),
+            ("X-RateLimit-Limit", "10"),
+            ("X-RateLimit-Remaining", "9"),
+            ("X-RateLimit-Reset", "1470173023.123"),
+            ("X-RateLimit-Reset-After", "64.57"),
+        ];
+
+        let info = RateLimitInfo::from_headers(headers.iter().copied()).unwrap();
+        assert_eq!(Some("abcd1234"), info.bucket.as_deref());
+        assert_eq!(Some(10), info.limit);
+        assert_eq!(Some(9), info.remaining);
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs_f64(1_470_173_023.123)),
+            info.reset
+        );
+        assert_eq!(Some(Duration::from_secs_f64(64.57)), info.reset_after);
+    }
+
+    #[test]
+    fn test_partial_headers() {
+        let headers = [("x-ratelimit-remaining", "0"), ("content-length", "12")];
+
+        let info = RateLimitInfo::from_headers(headers.iter().copied()).unwrap();
+        assert_eq!(
+            RateLimitInfo {
+                remaining: Some(0),
+                ..RateLimitInfo::default()
+            },
+            info
+        );
+    }
+
+    #[test]
+    fn test_malformed_headers() {
+        let headers = [
+            ("X-RateLimit-Limit", "ten"),
+            ("X-RateLimit-Remaining", "-1"),
+            ("X-RateLimit-Reset", "soon"),
+            ("X-RateLimit-Reset-After", "-5.0"),
+            ("X-RateLimit-Bucket", "abcd1234"),
+        ];
+
+        let info = RateLimitInfo::from_headers(headers.iter().copied()).unwrap();
+        assert_eq!(
+            RateLimitInfo {
+                bucket: Some("abcd1234".to_owned()),
+                ..RateLimitInfo::default()
+            },
+            info
+        );
+    }
+
+    #[test]
+    fn test_no_headers() {
+        let headers = [("Content-Type", "application/json")];
+
+        assert!(RateLimitInfo::from_headers(headers.iter().copied()).is_none());
+    }
+}
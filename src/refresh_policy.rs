@@ -0,0 +1,290 @@
+use super::{Clock, TokenResponse};
+use std::time::{Duration, SystemTime};
+
+/// When to refresh a token, relative to its lifetime.
+///
+/// Tokens are refreshed once a [fraction] of their lifetime has passed,
+/// which is 90% by default. An optional [jitter] window moves the refresh
+/// earlier by up to its length, so that many tokens issued at the same time
+/// aren't all refreshed in the same second. The jitter is derived from a
+/// hash of the access token, so it's the same every time it's calculated for
+/// a token, including by different workers.
+///
+/// The methods of [`TokenResponse`] use the default policy.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+/// use twilight_oauth2::{
+///     request::client_credentials_grant::ClientCredentialsGrantResponse, RefreshPolicy,
+/// };
+///
+/// let response = serde_json::from_str::<ClientCredentialsGrantResponse>(r#"{
+///     "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+///     "token_type": "Bearer",
+///     "expires_in": 1000,
+///     "scope": "identify"
+/// }"#)?;
+///
+/// let policy = RefreshPolicy::new().with_fraction(0.5);
+/// assert_eq!(Duration::from_secs(500), policy.refresh_in(&response));
+///
+/// let jittered = policy.with_jitter(Duration::from_secs(60)).refresh_in(&response);
+/// assert!(jittered <= Duration::from_secs(500));
+/// assert!(jittered >= Duration::from_secs(440));
+/// # Ok(()) }
+/// ```
+///
+/// [`TokenResponse`]: trait.TokenResponse.html
+/// [fraction]: #method.fraction
+/// [jitter]: #method.jitter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RefreshPolicy {
+    pub(crate) fraction: f64,
+    pub(crate) jitter: Duration,
+}
+
+impl RefreshPolicy {
+    /// Fraction of the lifetime after which tokens are refreshed by default.
+    pub const DEFAULT_FRACTION: f64 = 0.9;
+
+    /// Create a new policy refreshing after the [default fraction] of the
+    /// lifetime, without jitter.
+    ///
+    /// [default fraction]: #associatedconstant.DEFAULT_FRACTION
+    pub const fn new() -> Self {
+        Self {
+            fraction: Self::DEFAULT_FRACTION,
+            jitter: Duration::from_secs(0),
+        }
+    }
+
+    /// Set the fraction of the lifetime after which tokens are refreshed.
+    ///
+    /// Values outside of `0.0` to `1.0` are clamped, and `NaN` is treated as
+    /// `0.0`, refreshing immediately.
+    pub fn fraction(&mut self, fraction: f64) -> &mut Self {
+        self.fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        self
+    }
+
+    /// Set the fraction of the lifetime after which tokens are refreshed.
+    ///
+    /// This is the by-value form of [`fraction`], for building in one
+    /// expression.
+    ///
+    /// [`fraction`]: #method.fraction
+    #[must_use = "the policy is returned with the value set"]
+    pub fn with_fraction(mut self, fraction: f64) -> Self {
+        self.fraction(fraction);
+
+        self
+    }
+
+    /// Set the length of the window by which refreshes are moved earlier.
+    ///
+    /// A refresh is never moved to before the token was issued.
+    pub fn jitter(&mut self, jitter: Duration) -> &mut Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Set the length of the window by which refreshes are moved earlier.
+    ///
+    /// This is the by-value form of [`jitter`], for building in one
+    /// expression.
+    ///
+    /// [`jitter`]: #method.jitter
+    #[must_use = "the policy is returned with the value set"]
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter(jitter);
+
+        self
+    }
+
+    /// Return the amount of time from issuing after which the token of a
+    /// response should be refreshed.
+    ///
+    /// This is zero if the jitter is longer than the fraction of the
+    /// lifetime, meaning the token should be refreshed now.
+    pub fn refresh_in(&self, response: &impl TokenResponse) -> Duration {
+        let lifetime = Duration::from_secs(response.expires_in());
+
+        self.refresh_in_lifetime(lifetime, response.access_token().expose())
+    }
+
+    /// Return the point in time at which the token of a response received at
+    /// the clock's current time should be refreshed.
+    ///
+    /// A point in time too far in the future to be represented, such as for
+    /// a lifetime of `u64::MAX` seconds, saturates to one that can be.
+    pub fn refresh_at(&self, response: &impl TokenResponse, clock: &impl Clock) -> SystemTime {
+        let now = clock.now();
+        let mut refresh_in = self.refresh_in(response);
+
+        // `SystemTime` has no maximum to saturate to, so shorten the time
+        // until it can be added.
+        loop {
+            if let Some(refresh_at) = now.checked_add(refresh_in) {
+                return refresh_at;
+            }
+
+            refresh_in /= 2;
+        }
+    }
+
+    fn refresh_in_lifetime(&self, lifetime: Duration, token: &str) -> Duration {
+        // Multiplying overflows if the fraction rounds the lifetime up past
+        // the maximum `Duration`, in which case the fraction is 1.
+        let base =
+            Duration::try_from_secs_f64(lifetime.as_secs_f64() * self.fraction).unwrap_or(lifetime);
+
+        let window = self.jitter.as_millis();
+        if window == 0 {
+            return base;
+        }
+
+        let offset = u128::from(fnv1a(token.as_bytes())) % (window + 1);
+        // The offset is at most the jitter window, which is a `Duration`.
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = Duration::from_millis(offset as u64);
+
+        base.checked_sub(offset).unwrap_or_default()
+    }
+}
+
+impl Default for RefreshPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 64-bit FNV-1a hash, which unlike the standard library's hasher is stable
+/// across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, RefreshPolicy};
+    use crate::{
+        fixtures::{self, ManualClock},
+        request::client_credentials_grant::ClientCredentialsGrantResponse,
+        TokenResponse,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{
+        fmt::Debug,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    assert_impl_all!(RefreshPolicy: Clone, Copy, Debug, Default, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(b"a"));
+        assert_eq!(0x26c4_b17d_50b3_c152, fnv1a(b"token"));
+    }
+
+    #[test]
+    fn test_fraction() {
+        let lifetime = Duration::from_secs(1000);
+        let refresh_in = |fraction| {
+            RefreshPolicy::new()
+                .with_fraction(fraction)
+                .refresh_in_lifetime(lifetime, "a")
+                .as_secs()
+        };
+
+        assert_eq!(
+            900,
+            RefreshPolicy::new()
+                .refresh_in_lifetime(lifetime, "a")
+                .as_secs()
+        );
+        assert_eq!(250, refresh_in(0.25));
+        assert_eq!(1000, refresh_in(2.0));
+        assert_eq!(0, refresh_in(-1.0));
+        assert_eq!(0, refresh_in(f64::NAN));
+    }
+
+    #[test]
+    fn test_jitter() {
+        let policy = RefreshPolicy::new().with_jitter(Duration::from_secs(45));
+        let lifetime = Duration::from_secs(1000);
+
+        // fnv1a("a") % 45_001 is 26_616.
+        assert_eq!(
+            900_000 - 26_616,
+            policy.refresh_in_lifetime(lifetime, "a").as_millis()
+        );
+        // The same token always gets the same jitter, other tokens don't.
+        assert_eq!(
+            policy.refresh_in_lifetime(lifetime, "a"),
+            policy.refresh_in_lifetime(lifetime, "a")
+        );
+        assert_ne!(
+            policy.refresh_in_lifetime(lifetime, "a"),
+            policy.refresh_in_lifetime(lifetime, "b")
+        );
+    }
+
+    #[test]
+    fn test_short_lifetime_refreshes_now() {
+        let policy = RefreshPolicy::new().with_jitter(Duration::from_secs(45));
+
+        assert_eq!(
+            Duration::default(),
+            policy.refresh_in_lifetime(Duration::from_secs(10), "a")
+        );
+        assert_eq!(
+            Duration::default(),
+            RefreshPolicy::new().refresh_in_lifetime(Duration::default(), "a")
+        );
+    }
+
+    #[test]
+    fn test_lifetime_overflow() {
+        let response = ClientCredentialsGrantResponse {
+            expires_in: u64::MAX,
+            ..fixtures::client_credentials_grant_response()
+        };
+        let policy = RefreshPolicy::new().with_fraction(1.0);
+        assert_eq!(Duration::from_secs(u64::MAX), policy.refresh_in(&response));
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_001);
+        let clock = ManualClock::new(now);
+        assert!(policy.refresh_at(&response, &clock) > now);
+        assert!(RefreshPolicy::new().refresh_at(&response, &clock) > now);
+        assert!(response.refresh_at() > now);
+    }
+
+    #[test]
+    fn test_response() {
+        let response = fixtures::access_token_response();
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_001));
+        let refresh_at = RefreshPolicy::new().refresh_at(&response, &clock);
+
+        // 90% of the fixture's lifetime of a week.
+        assert_eq!(544_320, response.refresh_in().as_secs());
+        assert_eq!(
+            545_321,
+            refresh_at.duration_since(UNIX_EPOCH).unwrap().as_secs()
+        );
+    }
+}
@@ -1,31 +1,79 @@
 use crate::client::RedirectUriInvalidError;
 
 use super::super::{
+    form::{self, FormWriter},
     scope::{self, Scope},
-    Client, GrantType, TokenType,
+    AccessToken, Client, GrantType, RefreshToken, TokenResponse, TokenType,
 };
+#[cfg(feature = "model")]
+use super::webhook_token_exchange::RedactedWebhook;
+use super::{CurlValue, Header, Headers, Method, OAuthRequest};
+use crate::id::{self, ApplicationId};
 use serde::{Deserialize, Serialize};
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
+#[cfg(feature = "model")]
+use twilight_model::channel::Webhook;
 use url::Url;
-use std::fmt::Write;
-use twilight_model::{channel::Webhook, id::ApplicationId};
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+/// Body of an access token exchange request.
+///
+/// The `Display` implementation renders the fields like the form that is
+/// sent, unencoded and with the client secret and code replaced by
+/// `<redacted>`, so that it can be logged.
+///
+/// Bodies deserialize from the fields they serialize to, so that a proxy can
+/// read a request it received and forward it. Strings are borrowed from the
+/// input where possible, and the client ID may be a string or an integer.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct AccessTokenExchangeRequestBody<'a> {
     /// ID of the application that was authorized.
+    #[serde(deserialize_with = "id::deserialize_id")]
     pub client_id: ApplicationId,
     /// Secret of the application that was authorized.
-    pub client_secret: &'a str,
+    #[serde(borrow)]
+    pub client_secret: Cow<'a, str>,
     /// Access token used to perform requests on behalf of the authorized user.
-    pub code: &'a str,
+    #[serde(borrow)]
+    pub code: Cow<'a, str>,
+    /// PKCE code verifier whose challenge was sent in the authorization URL.
+    ///
+    /// This is only sent when set.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub code_verifier: Option<Cow<'a, str>>,
     /// Type of grant approval.
     pub grant_type: GrantType,
     /// Redirect URi that the user was redirected to.
-    pub redirect_uri: &'a str,
+    #[serde(borrow)]
+    pub redirect_uri: Cow<'a, str>,
     /// List of scopes that the user granted.
     ///
     /// This is space-delimited.
-    pub scope: String,
+    #[serde(borrow)]
+    pub scope: Cow<'a, str>,
+}
+
+impl Display for AccessTokenExchangeRequestBody<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("client_id=")?;
+        Display::fmt(&self.client_id, f)?;
+        f.write_str("&client_secret=<redacted>&code=<redacted>")?;
+
+        if self.code_verifier.is_some() {
+            f.write_str("&code_verifier=<redacted>")?;
+        }
+
+        f.write_str("&grant_type=")?;
+        f.write_str(self.grant_type.name())?;
+        f.write_str("&redirect_uri=")?;
+        f.write_str(&self.redirect_uri)?;
+        f.write_str("&scope=")?;
+
+        f.write_str(&self.scope)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -34,12 +82,25 @@ pub struct AccessTokenExchangeRequest<'a> {
     /// Body to send.
     pub body: AccessTokenExchangeRequestBody<'a>,
     /// Headers to send.
-    pub headers: &'static [(&'static str, &'static str)],
+    ///
+    /// This includes the [`USER_AGENT`].
+    ///
+    /// [`USER_AGENT`]: ../constant.USER_AGENT.html
+    pub headers: Headers,
     /// Base of the URL.
     pub url_base: &'static str,
 }
 
 impl AccessTokenExchangeRequest<'_> {
+    /// Return the HTTP method to send the request with.
+    ///
+    /// This is always [`Method::Post`].
+    ///
+    /// [`Method::Post`]: ../enum.Method.html#variant.Post
+    pub const fn method(&self) -> Method {
+        Method::Post
+    }
+
     /// Retrieve a URL with the body urlencoded as query parameters.
     ///
     /// This URL can be used to make a POST request with the specified
@@ -47,28 +108,135 @@ impl AccessTokenExchangeRequest<'_> {
     ///
     /// [`headers`]: #structfield.url_base
     pub fn url(&self) -> String {
-        let mut buf = self.url_base.to_owned();
-        buf.push_str("?client_id=");
-        let _ = write!(buf, "{}", self.body.client_id.0);
-        buf.push_str("&client_secret=");
-        buf.push_str(self.body.client_secret);
-        buf.push_str("&code=");
-        buf.push_str(self.body.code);
-        buf.push_str("&redirect_uri=");
-        buf.push_str(&urlencoding::encode(self.body.redirect_uri));
-        buf.push_str("&scope=");
-        buf.push_str(&urlencoding::encode(&self.body.scope));
+        let mut buf = String::new();
+        self.write_url(&mut buf);
 
         buf
     }
+
+    /// Append the URL returned by [`url`] to a buffer.
+    ///
+    /// Existing contents of the buffer are left untouched. This allows
+    /// reusing one buffer when building many URLs.
+    ///
+    /// [`url`]: #method.url
+    pub fn write_url(&self, buf: &mut String) {
+        form::append(buf, |form| {
+            form.push_raw(self.url_base);
+            form.push_raw("?");
+            self.write_form(form);
+        });
+    }
+
+    /// Render the request as a curl command, for debugging.
+    ///
+    /// Each body field is passed via `--data-urlencode`, with arguments
+    /// quoted for POSIX shells. Unless `unredacted` is set, the client secret,
+    /// code, and code verifier are replaced by the shell variables
+    /// `$CLIENT_SECRET`, `$CODE`, and `$CODE_VERIFIER`, so the command can be
+    /// shared without leaking them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_oauth2::{id::ApplicationId, Client};
+    ///
+    /// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+    /// let request = client
+    ///     .access_token_exchange("code", "https://example.com")
+    ///     .expect("redirect uri is configured")
+    ///     .build();
+    /// let curl = request.to_curl(false);
+    ///
+    /// assert!(curl.contains("\"client_secret=$CLIENT_SECRET\""));
+    /// assert!(!curl.contains("'client_secret=secret'"));
+    /// # Ok(()) }
+    /// ```
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        let client_id = self.body.client_id.0.to_string();
+
+        let mut fields = vec![
+            ("client_id", CurlValue::Plain(&client_id)),
+            (
+                "client_secret",
+                CurlValue::Secret(&self.body.client_secret, "CLIENT_SECRET"),
+            ),
+            ("code", CurlValue::Secret(&self.body.code, "CODE")),
+        ];
+
+        if let Some(code_verifier) = self.body.code_verifier.as_ref() {
+            fields.push((
+                "code_verifier",
+                CurlValue::Secret(code_verifier, "CODE_VERIFIER"),
+            ));
+        }
+
+        fields.push(("grant_type", CurlValue::Plain(self.body.grant_type.name())));
+        fields.push(("redirect_uri", CurlValue::Plain(&self.body.redirect_uri)));
+        fields.push(("scope", CurlValue::Plain(&self.body.scope)));
+
+        super::curl(
+            self.method(),
+            self.url_base,
+            &self.headers,
+            None,
+            &fields,
+            unredacted,
+        )
+    }
+
+    /// Urlencode the body.
+    fn form(&self) -> String {
+        form::build(|form| self.write_form(form))
+    }
+
+    fn write_form(&self, form: &mut dyn FormWriter) {
+        form.push_raw("client_id=");
+        form.push_number(self.body.client_id.0);
+        form.push_raw("&client_secret=");
+        form.push_encoded(&self.body.client_secret);
+        form.push_raw("&code=");
+        form.push_encoded(&self.body.code);
+
+        if let Some(code_verifier) = self.body.code_verifier.as_ref() {
+            form.push_raw("&code_verifier=");
+            form.push_encoded(code_verifier);
+        }
+
+        form.push_raw("&grant_type=");
+        form.push_encoded(self.body.grant_type.name());
+        form.push_raw("&redirect_uri=");
+        form.push_encoded(&self.body.redirect_uri);
+        form.push_raw("&scope=");
+        form.push_encoded(&self.body.scope);
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+impl OAuthRequest for AccessTokenExchangeRequest<'_> {
+    fn method(&self) -> Method {
+        AccessTokenExchangeRequest::method(self)
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.url_base)
+    }
+
+    fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.form())
+    }
+}
+
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct AccessTokenExchangeResponse {
     /// Access token to be used when making requests to the API on the user's
     /// behalf.
-    pub access_token: String,
+    pub access_token: AccessToken,
     /// Number of seconds from issuing that the access token is valid.
     ///
     /// After this duration, the refresh token must be exchanged for another
@@ -76,7 +244,7 @@ pub struct AccessTokenExchangeResponse {
     pub expires_in: u64,
     /// Refresh token to use to exchange for another access token and refresh
     /// token pair.
-    pub refresh_token: String,
+    pub refresh_token: RefreshToken,
     /// Space-delimited list of scopes that the token has had approved.
     pub scope: String,
     /// Type of token provided.
@@ -91,51 +259,335 @@ pub struct AccessTokenExchangeResponse {
     /// the [`WebhookIncoming`] scope was selected and approved.
     ///
     /// [`WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
+    #[cfg(feature = "model")]
+    pub webhook: Option<Webhook>,
+}
+
+#[cfg(feature = "json")]
+impl AccessTokenExchangeResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl Debug for AccessTokenExchangeResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("AccessTokenExchangeResponse");
+        debug.field("access_token", &self.access_token);
+        debug.field("expires_in", &self.expires_in);
+        debug.field("refresh_token", &self.refresh_token);
+        debug.field("scope", &self.scope);
+        debug.field("token_type", &self.token_type);
+
+        #[cfg(feature = "model")]
+        debug.field("webhook", &self.webhook.as_ref().map(RedactedWebhook));
+
+        debug.finish()
+    }
+}
+
+impl TokenResponse for AccessTokenExchangeResponse {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        Some(&self.refresh_token)
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+/// Borrowed form of [`AccessTokenExchangeResponse`].
+///
+/// Strings borrow from the deserialized input when possible, such as when
+/// parsing a JSON body without escape sequences, and are owned otherwise.
+/// Use [`into_owned`] to convert it into an [`AccessTokenExchangeResponse`].
+///
+/// Tokens are redacted from the `Debug` output. Unlike [`AccessToken`] and
+/// [`RefreshToken`], borrowed tokens aren't zeroed when dropped.
+///
+/// [`AccessTokenExchangeResponse`]: struct.AccessTokenExchangeResponse.html
+/// [`AccessToken`]: ../../struct.AccessToken.html
+/// [`RefreshToken`]: ../../struct.RefreshToken.html
+/// [`into_owned`]: #method.into_owned
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct AccessTokenExchangeResponseRef<'a> {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    #[serde(borrow)]
+    pub access_token: Cow<'a, str>,
+    /// Number of seconds from issuing that the access token is valid.
+    pub expires_in: u64,
+    /// Refresh token to use to exchange for another access token and refresh
+    /// token pair.
+    #[serde(borrow)]
+    pub refresh_token: Cow<'a, str>,
+    /// Space-delimited list of scopes that the token has had approved.
+    #[serde(borrow)]
+    pub scope: Cow<'a, str>,
+    /// Type of token provided.
+    pub token_type: TokenType,
+    /// Information about the webhook that was created by user authorization.
+    ///
+    /// This is always owned.
+    #[cfg(feature = "model")]
     pub webhook: Option<Webhook>,
 }
 
+impl AccessTokenExchangeResponseRef<'_> {
+    /// Convert into an owned response, allocating any borrowed strings.
+    pub fn into_owned(self) -> AccessTokenExchangeResponse {
+        AccessTokenExchangeResponse {
+            access_token: AccessToken::new(self.access_token),
+            expires_in: self.expires_in,
+            refresh_token: RefreshToken::new(self.refresh_token),
+            scope: self.scope.into_owned(),
+            token_type: self.token_type,
+            #[cfg(feature = "model")]
+            webhook: self.webhook,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> AccessTokenExchangeResponseRef<'a> {
+    /// Parse a response from a JSON body, borrowing strings from it when
+    /// possible.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &'a [u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl Debug for AccessTokenExchangeResponseRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("AccessTokenExchangeResponseRef");
+        debug.field("access_token", &format_args!("<redacted>"));
+        debug.field("expires_in", &self.expires_in);
+        debug.field("refresh_token", &format_args!("<redacted>"));
+        debug.field("scope", &self.scope);
+        debug.field("token_type", &self.token_type);
+
+        #[cfg(feature = "model")]
+        debug.field("webhook", &self.webhook.as_ref().map(RedactedWebhook));
+
+        debug.finish()
+    }
+}
+
+const BASE_URL: &str = "https://discord.com/api/v6/oauth2/token";
+
+pub(crate) fn build<'a>(
+    client: &'a Client,
+    code: &'a str,
+    code_verifier: Option<&'a str>,
+    redirect_uri: &'a Url,
+    scopes: &[Scope],
+) -> AccessTokenExchangeRequest<'a> {
+    let scope = scope::join_cow(scopes);
+    let redirect_uri = client.redirect_uri_text(redirect_uri);
+
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "oauth2.exchange_code",
+        application_id = client.client_id().0,
+        grant_type = GrantType::AuthorizationCode.name(),
+        redirect_uri,
+        scopes = scope.as_ref(),
+    )
+    .entered();
+    let request = AccessTokenExchangeRequest {
+        body: AccessTokenExchangeRequestBody {
+            client_id: client.client_id(),
+            client_secret: Cow::Borrowed(client.client_secret()),
+            code: Cow::Borrowed(code),
+            code_verifier: code_verifier.map(Cow::Borrowed),
+            grant_type: GrantType::AuthorizationCode,
+            redirect_uri: Cow::Borrowed(redirect_uri),
+            scope,
+        },
+        headers: client.headers(),
+        url_base: BASE_URL,
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(body = %request.body, "built access token exchange request");
+
+    request
+}
+
 #[derive(Clone, Debug)]
 pub struct AccessTokenExchangeBuilder<'a> {
     client: &'a Client,
     code: &'a str,
+    code_verifier: Option<&'a str>,
     redirect_uri: &'a Url,
-    scopes: Option<&'a [Scope]>,
+    scopes: Vec<Scope>,
 }
 
 impl<'a> AccessTokenExchangeBuilder<'a> {
-    const BASE_URL: &'static str = "https://discord.com/api/v6/oauth2/token";
-
-    pub(crate) fn new(client: &'a Client, code: &'a str, redirect_uri: &'a str) -> Result<Self, RedirectUriInvalidError<'a>> {
+    pub(crate) fn new(
+        client: &'a Client,
+        code: &'a str,
+        redirect_uri: &'a str,
+    ) -> Result<Self, RedirectUriInvalidError<'a>> {
         let redirect_uri = client.redirect_uri(redirect_uri)?;
 
         Ok(Self {
             client,
             code,
+            code_verifier: None,
             redirect_uri,
-            scopes: None,
+            scopes: client.default_scopes().to_vec(),
         })
     }
 
-    pub fn build(&'a self) -> AccessTokenExchangeRequest<'a> {
-        let scope = self.scopes.map(scope::join).unwrap_or_default();
-
-        AccessTokenExchangeRequest {
-            body: AccessTokenExchangeRequestBody {
-                client_id: self.client.client_id(),
-                client_secret: self.client.client_secret(),
-                code: self.code,
-                grant_type: GrantType::AuthorizationCode,
-                redirect_uri: self.redirect_uri.as_ref(),
-                scope,
-            },
-            headers: &[("Content-Type", "application/x-www-form-urlencoded")],
-            url_base: Self::BASE_URL,
+    pub fn build(&self) -> AccessTokenExchangeRequest<'a> {
+        build(
+            self.client,
+            self.code,
+            self.code_verifier,
+            self.redirect_uri,
+            &self.scopes,
+        )
+    }
+
+    /// Take ownership of the code, returning a builder which doesn't borrow
+    /// anything.
+    ///
+    /// This is useful when the code is only available for part of an
+    /// asynchronous handler, such as when it's extracted from the query of
+    /// the callback request. The client is cloned into the builder.
+    ///
+    /// The code verifier and scopes set on this builder are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::thread;
+    /// use twilight_oauth2::{id::ApplicationId, Client};
+    ///
+    /// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+    /// let code = String::from("code from the callback");
+    ///
+    /// let builder = client
+    ///     .access_token_exchange(&code, "https://example.com")
+    ///     .expect("redirect uri is configured")
+    ///     .with_code_owned(code.clone());
+    /// drop(code);
+    ///
+    /// let url = thread::spawn(move || builder.build().url()).join().unwrap();
+    /// # Ok(()) }
+    /// ```
+    pub fn with_code_owned(self, code: String) -> AccessTokenExchangeBuilderOwned {
+        AccessTokenExchangeBuilderOwned {
+            client: self.client.clone(),
+            code,
+            code_verifier: self.code_verifier.map(ToOwned::to_owned),
+            redirect_uri: self.redirect_uri.clone(),
+            scopes: self.scopes,
         }
     }
 
+    /// Set the PKCE code verifier whose challenge was sent in the
+    /// authorization URL.
+    ///
+    /// Refer to [RFC 7636] for additional information.
+    ///
+    /// [RFC 7636]: https://tools.ietf.org/html/rfc7636
+    pub fn code_verifier(&mut self, code_verifier: &'a str) -> &mut Self {
+        self.code_verifier.replace(code_verifier);
+
+        self
+    }
+
+    /// Set the PKCE code verifier whose challenge was sent in the
+    /// authorization URL.
+    ///
+    /// This is the by-value form of [`code_verifier`], for building in one
+    /// expression.
+    ///
+    /// [`code_verifier`]: #method.code_verifier
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_code_verifier(mut self, code_verifier: &'a str) -> Self {
+        self.code_verifier(code_verifier);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the access token exchange request.
+    ///
+    /// This appends to the client's [default scopes] or the scopes previously
+    /// set via [`scopes`].
+    ///
+    /// [`scopes`]: #method.scopes
+    /// [default scopes]: ../../client/struct.Client.html#method.with_default_scopes
+    pub fn add_scope(&mut self, scope: Scope) -> &mut Self {
+        self.scopes.push(scope);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the access token exchange request.
+    ///
+    /// This is the by-value form of [`add_scope`], for building in one
+    /// expression.
+    ///
+    /// [`add_scope`]: #method.add_scope
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.add_scope(scope);
+
+        self
+    }
+
     /// Set the scopes for the access token exchange request.
     ///
     /// This must be the same scopes you requested in the authorization URL.
+    /// This overrides the client's [default scopes].
+    ///
+    /// Scopes or references to scopes can be passed from any iterator, such
+    /// as an array, a slice, or an iterator chain. They're copied into a
+    /// vector owned by the builder.
     ///
     /// Read about Discord's [scope documentation].
     ///
@@ -143,9 +595,94 @@ impl<'a> AccessTokenExchangeBuilder<'a> {
     ///
     /// [`Bot`]: enum.Scope.html#variant.Bot
     /// [RFC 6749 § 3.3]: https://tools.ietf.org/html/rfc6749#section-3.3
+    /// [default scopes]: ../../client/struct.Client.html#method.with_default_scopes
     /// [scope documentation]: https://discord.com/developers/docs/topics/oauth2#shared-resources-oauth2-scopes
-    pub fn scopes(&mut self, scopes: &'a [Scope]) -> &mut Self {
-        self.scopes.replace(scopes);
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes = scopes.into_iter().map(|scope| *scope.borrow()).collect();
+
+        self
+    }
+
+    /// Set the scopes for the access token exchange request.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
+
+        self
+    }
+}
+
+/// Access token exchange builder owning the code and the client.
+///
+/// This is created via [`AccessTokenExchangeBuilder::with_code_owned`].
+///
+/// [`AccessTokenExchangeBuilder::with_code_owned`]: struct.AccessTokenExchangeBuilder.html#method.with_code_owned
+#[derive(Clone, Debug)]
+pub struct AccessTokenExchangeBuilderOwned {
+    client: Client,
+    code: String,
+    code_verifier: Option<String>,
+    redirect_uri: Url,
+    scopes: Vec<Scope>,
+}
+
+impl AccessTokenExchangeBuilderOwned {
+    pub fn build(&self) -> AccessTokenExchangeRequest<'_> {
+        build(
+            &self.client,
+            &self.code,
+            self.code_verifier.as_deref(),
+            &self.redirect_uri,
+            &self.scopes,
+        )
+    }
+
+    /// Add a scope to the scopes for the access token exchange request.
+    ///
+    /// This appends to the scopes previously set.
+    pub fn add_scope(&mut self, scope: Scope) -> &mut Self {
+        self.scopes.push(scope);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the access token exchange request.
+    ///
+    /// This is the by-value form of [`add_scope`], for building in one
+    /// expression.
+    ///
+    /// [`add_scope`]: #method.add_scope
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.add_scope(scope);
+
+        self
+    }
+
+    /// Set the scopes for the access token exchange request.
+    ///
+    /// This must be the same scopes you requested in the authorization URL.
+    /// Scopes or references to scopes can be passed from any iterator.
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes = scopes.into_iter().map(|scope| *scope.borrow()).collect();
+
+        self
+    }
+
+    /// Set the scopes for the access token exchange request.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
 
         self
     }
@@ -154,31 +691,48 @@ impl<'a> AccessTokenExchangeBuilder<'a> {
 #[cfg(test)]
 mod tests {
     use super::{
-        AccessTokenExchangeBuilder, AccessTokenExchangeRequest, AccessTokenExchangeRequestBody,
-        AccessTokenExchangeResponse,
+        AccessTokenExchangeBuilder, AccessTokenExchangeBuilderOwned, AccessTokenExchangeRequest,
+        AccessTokenExchangeRequestBody, AccessTokenExchangeResponse,
+        AccessTokenExchangeResponseRef,
+    };
+    use crate::id::ApplicationId;
+    use crate::{
+        fixtures,
+        request::{Method, OAuthRequest, USER_AGENT},
+        Client, Scope,
     };
-    use crate::TokenType;
     use serde::{Deserialize, Serialize};
-    use serde_test::Token;
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::fmt::Debug;
-    use twilight_model::{
-        channel::{Webhook, WebhookType},
-        id::{ChannelId, GuildId, WebhookId},
+    use std::{
+        borrow::Cow,
+        fmt::{Debug, Display},
+        thread,
     };
 
-    assert_fields!(AccessTokenExchangeRequestBody<'_>: client_id, client_secret, code, grant_type, redirect_uri, scope);
+    assert_fields!(AccessTokenExchangeRequestBody<'_>: client_id, client_secret, code, code_verifier, grant_type, redirect_uri, scope);
     assert_fields!(AccessTokenExchangeRequest<'_>: body, headers, url_base);
     assert_fields!(
         AccessTokenExchangeResponse: access_token,
         expires_in,
         refresh_token,
         scope,
-        token_type,
-        webhook
+        token_type
     );
+    #[cfg(feature = "model")]
+    assert_fields!(AccessTokenExchangeResponse: webhook);
     assert_impl_all!(AccessTokenExchangeBuilder<'_>: Clone, Debug, Send, Sync);
-    assert_impl_all!(AccessTokenExchangeRequestBody<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(AccessTokenExchangeBuilderOwned: Clone, Debug, Send, Sync);
+    assert_impl_all!(
+        AccessTokenExchangeRequestBody<'static>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Display,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
     assert_impl_all!(AccessTokenExchangeRequest<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
     assert_impl_all!(
         AccessTokenExchangeResponse: Clone,
@@ -190,14 +744,158 @@ mod tests {
         Serialize,
         Sync
     );
+    assert_fields!(
+        AccessTokenExchangeResponseRef<'_>: access_token,
+        expires_in,
+        refresh_token,
+        scope,
+        token_type
+    );
+    assert_impl_all!(
+        AccessTokenExchangeResponseRef<'_>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_response_ref() {
+        let response: AccessTokenExchangeResponseRef<'_> =
+            serde_json::from_slice(fixtures::ACCESS_TOKEN_RESPONSE).unwrap();
+        assert!(matches!(response.access_token, Cow::Borrowed(_)));
+        assert!(matches!(response.refresh_token, Cow::Borrowed(_)));
+
+        let debug = format!("{response:?}");
+        assert!(!debug.contains("6qrZcUqja7812RVdnEKjpzOL4CvHBFG"));
+        assert!(!debug.contains("D43f5y0ahjqew82jZ4NViEr2YafMKhue"));
+        assert_eq!(fixtures::access_token_response(), response.into_owned());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::{
+            io::{Result as IoResult, Write},
+            sync::{Arc, Mutex},
+        };
+        use tracing::Level;
+
+        #[derive(Clone, Default)]
+        struct Output(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Output {
+            fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> IoResult<()> {
+                Ok(())
+            }
+        }
+
+        let output = Output::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_max_level(Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let builder = client
+            .access_token_exchange("code1234", "https://example.com")
+            .unwrap();
+        tracing::subscriber::with_default(subscriber, || builder.build());
+
+        let logs = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("oauth2.exchange_code"));
+        assert!(logs.contains("grant_type=\"authorization_code\""));
+        assert!(logs.contains("application_id=1"));
+        assert!(logs.contains("client_secret=<redacted>"));
+        assert!(logs.contains("redirect_uri=https://example.com"));
+        assert!(!logs.contains("hunter2"));
+        assert!(!logs.contains("code1234"));
+    }
 
+    #[test]
+    fn test_method() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let builder = client
+            .access_token_exchange("b", "https://example.com")
+            .unwrap();
+
+        assert_eq!(Method::Post, builder.build().method());
+    }
+
+    #[test]
+    fn test_write_url() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client
+            .access_token_exchange("b", "https://example.com")
+            .unwrap();
+        let mut buf = "existing".to_owned();
+
+        let first = builder.build();
+        first.write_url(&mut buf);
+        builder.scopes([Scope::Identify, Scope::Email]);
+        let second = builder.build();
+        second.write_url(&mut buf);
+
+        assert_eq!(format!("existing{}{}", first.url(), second.url()), buf);
+    }
+
+    #[test]
+    fn test_with_code_owned() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client
+            .access_token_exchange("b", "https://example.com")
+            .unwrap();
+        builder.scopes([Scope::Identify]);
+        let expected = builder.build().url();
+
+        let code = "b".to_owned();
+        let owned = builder.with_code_owned(code);
+        let url = thread::spawn(move || owned.build().url()).join().unwrap();
+        assert_eq!(expected, url);
+    }
+
+    #[test]
+    fn test_url_encoded() {
+        let redirect_uri = "https://example.com/cb?source=discord";
+        let client = Client::new(ApplicationId(1), "a+b%c", &[redirect_uri]).unwrap();
+        let builder = client
+            .access_token_exchange("abc+def", redirect_uri)
+            .unwrap();
+        let expected = "https://discord.com/api/v6/oauth2/token?\
+            client_id=1\
+            &client_secret=a%2Bb%25c\
+            &code=abc%2Bdef\
+            &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fexample.com%2Fcb%3Fsource%3Ddiscord\
+            &scope=";
+        let url = builder.build().url();
+        assert_eq!(expected, url);
+        assert_eq!(url.len(), url.capacity());
+    }
+
+    #[cfg(feature = "model")]
     #[test]
     fn test_response_webhook() {
+        use crate::{AccessToken, RefreshToken, TokenType};
+        use serde_test::Token;
+        use twilight_model::{
+            channel::{Webhook, WebhookType},
+            id::{ChannelId, GuildId, WebhookId},
+        };
+
         let actual = AccessTokenExchangeResponse {
-            access_token: "a".to_owned(),
+            access_token: AccessToken::new("a"),
             expires_in: 604_800,
             token_type: TokenType::Bearer,
-            refresh_token: "b".to_owned(),
+            refresh_token: RefreshToken::new("b"),
             scope: "webhook.incoming".to_owned(),
             webhook: Some(Webhook {
                 avatar: None,
@@ -235,7 +933,7 @@ mod tests {
                 Token::Some,
                 Token::Struct {
                     name: "Webhook",
-                    len: 8,
+                    len: 7,
                 },
                 Token::Str("avatar"),
                 Token::None,
@@ -257,11 +955,161 @@ mod tests {
                 Token::Str("token"),
                 Token::Some,
                 Token::Str("token"),
-                Token::Str("user"),
-                Token::None,
                 Token::StructEnd,
                 Token::StructEnd,
             ],
         );
     }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+
+        let mut builder = client
+            .access_token_exchange("code", "https://example.com")
+            .unwrap();
+        builder.scopes([Scope::Identify]).add_scope(Scope::Email);
+        let fluent = client
+            .access_token_exchange("code", "https://example.com")
+            .unwrap()
+            .with_scopes([Scope::Identify])
+            .with_scope(Scope::Email);
+        assert_eq!(builder.build(), fluent.build());
+
+        let mut owned = fluent.clone().with_code_owned("code".to_owned());
+        owned.scopes([Scope::Guilds]).add_scope(Scope::Email);
+        let owned_fluent = fluent
+            .with_code_owned("code".to_owned())
+            .with_scopes([Scope::Guilds])
+            .with_scope(Scope::Email);
+        assert_eq!(owned.build(), owned_fluent.build());
+    }
+
+    #[test]
+    fn test_body_display() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client
+            .access_token_exchange("code1234", "https://example.com")
+            .unwrap()
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+        let display = request.body.to_string();
+
+        assert_eq!(
+            "client_id=1&client_secret=<redacted>&code=<redacted>\
+            &grant_type=authorization_code&redirect_uri=https://example.com/\
+            &scope=identify email",
+            display
+        );
+        assert!(!display.contains("hunter2"));
+        assert!(!display.contains("code1234"));
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client
+            .access_token_exchange("code1234", "https://example.com")
+            .unwrap()
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+        let curl = request.to_curl(false);
+
+        assert_eq!(
+            format!(
+                "curl -X POST 'https://discord.com/api/v6/oauth2/token' \\\n  \
+                -H 'Content-Type: application/x-www-form-urlencoded' \\\n  \
+                -H 'User-Agent: {USER_AGENT}' \\\n  \
+                --data-urlencode 'client_id=1' \\\n  \
+                --data-urlencode \"client_secret=$CLIENT_SECRET\" \\\n  \
+                --data-urlencode \"code=$CODE\" \\\n  \
+                --data-urlencode 'grant_type=authorization_code' \\\n  \
+                --data-urlencode 'redirect_uri=https://example.com/' \\\n  \
+                --data-urlencode 'scope=identify email'"
+            ),
+            curl
+        );
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains("code1234"));
+
+        let curl = request.to_curl(true);
+        assert!(curl.contains("--data-urlencode 'client_secret=hunter2'"));
+        assert!(curl.contains("--data-urlencode 'code=code1234'"));
+    }
+
+    #[test]
+    fn test_code_verifier() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let builder = client
+            .access_token_exchange("code", "https://example.com")
+            .unwrap()
+            .with_code_verifier("verifier~1");
+        let request = builder.build();
+
+        assert_eq!(
+            "client_id=1&client_secret=a&code=code&code_verifier=verifier~1\
+            &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fexample.com%2F&scope=",
+            request.body().unwrap()
+        );
+        assert!(request
+            .body
+            .to_string()
+            .contains("&code=<redacted>&code_verifier=<redacted>&"));
+        assert!(request
+            .to_curl(false)
+            .contains("--data-urlencode \"code_verifier=$CODE_VERIFIER\""));
+
+        let json = serde_json::to_string(&request.body).unwrap();
+        assert_eq!(
+            request.body,
+            serde_json::from_str::<AccessTokenExchangeRequestBody<'_>>(&json).unwrap()
+        );
+
+        let owned = builder.with_code_owned("code".to_owned());
+        assert_eq!(request, owned.build());
+    }
+
+    #[test]
+    fn test_body_round_trip() {
+        let client = Client::new(ApplicationId(1), "hunter 2&", &["https://example.com"]).unwrap();
+        let request = client
+            .access_token_exchange("code1234", "https://example.com")
+            .unwrap()
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+
+        let json = serde_json::to_string(&request.body).unwrap();
+        let from_json = serde_json::from_str::<AccessTokenExchangeRequestBody<'_>>(&json).unwrap();
+        assert_eq!(request.body, from_json);
+        assert!(matches!(from_json.code, Cow::Borrowed("code1234")));
+
+        // The secret needs no escaping in JSON, but an escaped one can't be
+        // borrowed.
+        let escaped = json.replace("hunter 2&", "hunter\\u0020");
+        let from_escaped =
+            serde_json::from_str::<AccessTokenExchangeRequestBody<'_>>(&escaped).unwrap();
+        assert!(
+            matches!(from_escaped.client_secret, Cow::Owned(ref secret) if secret == "hunter ")
+        );
+    }
+
+    #[test]
+    fn test_body_form_round_trip() {
+        let client = Client::new(
+            ApplicationId(1),
+            "hunter 2&",
+            &["https://example.com/cb?a=b"],
+        )
+        .unwrap();
+        let request = client
+            .access_token_exchange("code+1234", "https://example.com/cb?a=b")
+            .unwrap()
+            .with_code_verifier("verifier~1")
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+
+        let json = crate::request::tests::form_to_json(&request.body().unwrap());
+        let from_form = serde_json::from_str::<AccessTokenExchangeRequestBody<'_>>(&json).unwrap();
+        assert_eq!(request.body, from_form);
+    }
 }
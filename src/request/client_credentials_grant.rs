@@ -1,23 +1,53 @@
 use super::super::{
+    form::{self, FormWriter},
     scope::{self, Scope},
-    Client, GrantType, TokenType,
+    AccessToken, Client, GrantType, RefreshToken, TokenResponse, TokenType,
 };
+use super::{CurlValue, Header, Headers, Method, OAuthRequest};
+use crate::id::{self, ApplicationId};
 use serde::{Deserialize, Serialize};
-use twilight_model::id::ApplicationId;
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+/// Body of a client credentials grant request.
+///
+/// The `Display` implementation renders the fields like the form that is
+/// sent, unencoded and with the client secret replaced by `<redacted>`, so
+/// that it can be logged.
+///
+/// Bodies deserialize from the fields they serialize to, so that a proxy can
+/// read a request it received and forward it. Strings are borrowed from the
+/// input where possible, and the client ID may be a string or an integer.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct ClientCredentialsGrantRequestBody<'a> {
     /// ID of the application that was authorized.
+    #[serde(deserialize_with = "id::deserialize_id")]
     pub client_id: ApplicationId,
     /// Secret of the application that was authorized.
-    pub client_secret: &'a str,
+    #[serde(borrow)]
+    pub client_secret: Cow<'a, str>,
     /// Type of grant approval.
     pub grant_type: GrantType,
     /// List of scopes that the user granted.
     ///
     /// This is space-delimited.
-    pub scope: String,
+    #[serde(borrow)]
+    pub scope: Cow<'a, str>,
+}
+
+impl Display for ClientCredentialsGrantRequestBody<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("client_id=")?;
+        Display::fmt(&self.client_id, f)?;
+        f.write_str("&client_secret=<redacted>&grant_type=")?;
+        f.write_str(self.grant_type.name())?;
+        f.write_str("&scope=")?;
+
+        f.write_str(&self.scope)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -26,7 +56,12 @@ pub struct ClientCredentialsGrantRequest<'a> {
     /// Body to send.
     pub body: ClientCredentialsGrantRequestBody<'a>,
     /// Headers to send.
-    pub headers: &'static [(&'static str, &'static str)],
+    ///
+    /// This includes the [`USER_AGENT`] and the `Authorization` header
+    /// carrying the client ID and secret, which aren't sent in the body.
+    ///
+    /// [`USER_AGENT`]: ../constant.USER_AGENT.html
+    pub headers: Headers,
     /// Base of the URL.
     ///
     /// Use the [`url`] method for the full URL with query parameters.
@@ -36,6 +71,15 @@ pub struct ClientCredentialsGrantRequest<'a> {
 }
 
 impl ClientCredentialsGrantRequest<'_> {
+    /// Return the HTTP method to send the request with.
+    ///
+    /// This is always [`Method::Post`].
+    ///
+    /// [`Method::Post`]: ../enum.Method.html#variant.Post
+    pub const fn method(&self) -> Method {
+        Method::Post
+    }
+
     /// Retrieve a URL with the body urlencoded as query parameters.
     ///
     /// This URL can be used to make a POST request with the specified
@@ -43,16 +87,86 @@ impl ClientCredentialsGrantRequest<'_> {
     ///
     /// [`headers`]: #structfield.url_base
     pub fn url(&self) -> String {
-        let mut buf = self.url_base.to_owned();
-        buf.push_str("?grant_type=");
-        buf.push_str(self.body.grant_type.name());
+        let mut buf = String::new();
+        self.write_url(&mut buf);
+
+        buf
+    }
+
+    /// Append the URL returned by [`url`] to a buffer.
+    ///
+    /// Existing contents of the buffer are left untouched. This allows
+    /// reusing one buffer when building many URLs.
+    ///
+    /// [`url`]: #method.url
+    pub fn write_url(&self, buf: &mut String) {
+        form::append(buf, |form| {
+            form.push_raw(self.url_base);
+            form.push_raw("?");
+            self.write_form(form);
+        });
+    }
+
+    /// Render the request as a curl command, for debugging.
+    ///
+    /// Each body field is passed via `--data-urlencode`, with arguments
+    /// quoted for POSIX shells. The client ID and secret are passed via `-u`,
+    /// since this grant authenticates with HTTP Basic authentication. Unless
+    /// `unredacted` is set, the client secret is replaced by the shell
+    /// variable `$CLIENT_SECRET`, so the command can be shared without
+    /// leaking it.
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        let client_id = self.body.client_id.0.to_string();
+        let mut fields = vec![("grant_type", CurlValue::Plain(self.body.grant_type.name()))];
 
         if !self.body.scope.is_empty() {
-            buf.push_str("&scope=");
-            buf.push_str(&urlencoding::encode(&self.body.scope));
+            fields.push(("scope", CurlValue::Plain(&self.body.scope)));
         }
 
-        buf
+        super::curl(
+            self.method(),
+            self.url_base,
+            &self.headers,
+            Some((
+                &client_id,
+                CurlValue::Secret(&self.body.client_secret, "CLIENT_SECRET"),
+            )),
+            &fields,
+            unredacted,
+        )
+    }
+
+    /// Urlencode the body.
+    fn form(&self) -> String {
+        form::build(|form| self.write_form(form))
+    }
+
+    fn write_form(&self, form: &mut dyn FormWriter) {
+        form.push_raw("grant_type=");
+        form.push_raw(self.body.grant_type.name());
+
+        if !self.body.scope.is_empty() {
+            form.push_raw("&scope=");
+            form.push_encoded(&self.body.scope);
+        }
+    }
+}
+
+impl OAuthRequest for ClientCredentialsGrantRequest<'_> {
+    fn method(&self) -> Method {
+        ClientCredentialsGrantRequest::method(self)
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.url_base)
+    }
+
+    fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.form())
     }
 }
 
@@ -61,7 +175,7 @@ impl ClientCredentialsGrantRequest<'_> {
 pub struct ClientCredentialsGrantResponse {
     /// Access token to be used when making requests to the API on the user's
     /// behalf.
-    pub access_token: String,
+    pub access_token: AccessToken,
     /// Number of seconds from issuing that the access token is valid.
     ///
     /// After this duration, the refresh token must be exchanged for another
@@ -77,6 +191,123 @@ pub struct ClientCredentialsGrantResponse {
     pub scope: String,
 }
 
+#[cfg(feature = "json")]
+impl ClientCredentialsGrantResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl TokenResponse for ClientCredentialsGrantResponse {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        None
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+/// Borrowed form of [`ClientCredentialsGrantResponse`].
+///
+/// Strings borrow from the deserialized input when possible, such as when
+/// parsing a JSON body without escape sequences, and are owned otherwise.
+/// Use [`into_owned`] to convert it into an [`ClientCredentialsGrantResponse`].
+///
+/// The access token is redacted from the `Debug` output. Unlike an
+/// [`AccessToken`], it isn't zeroed when dropped.
+///
+/// [`ClientCredentialsGrantResponse`]: struct.ClientCredentialsGrantResponse.html
+/// [`AccessToken`]: ../../struct.AccessToken.html
+/// [`into_owned`]: #method.into_owned
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ClientCredentialsGrantResponseRef<'a> {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    #[serde(borrow)]
+    pub access_token: Cow<'a, str>,
+    /// Number of seconds from issuing that the access token is valid.
+    pub expires_in: u64,
+    /// Type of token provided.
+    pub token_type: TokenType,
+    /// Space-delimited list of scopes that the token has had approved.
+    #[serde(borrow)]
+    pub scope: Cow<'a, str>,
+}
+
+impl ClientCredentialsGrantResponseRef<'_> {
+    /// Convert into an owned response, allocating any borrowed strings.
+    pub fn into_owned(self) -> ClientCredentialsGrantResponse {
+        ClientCredentialsGrantResponse {
+            access_token: AccessToken::new(self.access_token),
+            expires_in: self.expires_in,
+            token_type: self.token_type,
+            scope: self.scope.into_owned(),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> ClientCredentialsGrantResponseRef<'a> {
+    /// Parse a response from a JSON body, borrowing strings from it when
+    /// possible.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &'a [u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl Debug for ClientCredentialsGrantResponseRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("ClientCredentialsGrantResponseRef");
+        debug.field("access_token", &format_args!("<redacted>"));
+        debug.field("expires_in", &self.expires_in);
+        debug.field("token_type", &self.token_type);
+        debug.field("scope", &self.scope);
+
+        debug.finish()
+    }
+}
+
 /// Create a client credentials grant request.
 ///
 /// This can be used to quickly create a Bearer access token for the bot's
@@ -89,15 +320,16 @@ pub struct ClientCredentialsGrantResponse {
 ///
 /// ```
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// use twilight_model::id::ApplicationId;
-/// use twilight_oauth2::Client;
+/// use twilight_oauth2::{id::ApplicationId, Client, Scope};
 ///
 /// let application_id = ApplicationId(123);
 /// let client_secret = "abcdef01234567890";
 ///
 /// let client = Client::new(application_id, client_secret, &["https://example.com"])?;
-/// let mut url_builder = client.client_credentials_grant();
-/// let request = url_builder.build();
+/// let request = client
+///     .client_credentials_grant()
+///     .with_scopes([Scope::Identify, Scope::Connections])
+///     .build();
 ///
 /// println!("grant url: {}", request.url());
 /// # Ok(()) }
@@ -105,7 +337,7 @@ pub struct ClientCredentialsGrantResponse {
 #[derive(Clone, Debug)]
 pub struct ClientCredentialsGrantBuilder<'a> {
     client: &'a Client,
-    scopes: &'a [Scope],
+    scopes: Vec<Scope>,
 }
 
 impl<'a> ClientCredentialsGrantBuilder<'a> {
@@ -114,27 +346,76 @@ impl<'a> ClientCredentialsGrantBuilder<'a> {
     pub(crate) fn new(client: &'a Client) -> Self {
         Self {
             client,
-            scopes: &[Scope::Identify],
+            scopes: if client.default_scopes().is_empty() {
+                vec![Scope::Identify]
+            } else {
+                client.default_scopes().to_vec()
+            },
         }
     }
 
     /// Build a client credentials grant URL.
-    pub fn build(&'a self) -> ClientCredentialsGrantRequest<'a> {
-        ClientCredentialsGrantRequest {
+    pub fn build(&self) -> ClientCredentialsGrantRequest<'a> {
+        let scope = scope::join_cow(&self.scopes);
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "oauth2.client_credentials",
+            application_id = self.client.client_id().0,
+            grant_type = GrantType::ClientCredentials.name(),
+            scopes = scope.as_ref(),
+        )
+        .entered();
+        let request = ClientCredentialsGrantRequest {
             body: ClientCredentialsGrantRequestBody {
                 client_id: self.client.client_id(),
-                client_secret: self.client.client_secret(),
+                client_secret: Cow::Borrowed(self.client.client_secret()),
                 grant_type: GrantType::ClientCredentials,
-                scope: scope::join(self.scopes),
+                scope,
             },
-            headers: &[("Content-Type", "application/x-www-form-urlencoded")],
+            headers: self.client.basic_auth_headers(),
             url_base: Self::BASE_URL,
-        }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(body = %request.body, "built client credentials grant request");
+
+        request
+    }
+
+    /// Add a scope to the scopes for the client credentials grant request.
+    ///
+    /// This appends to the default scopes or the scopes previously set via
+    /// [`scopes`].
+    ///
+    /// [`scopes`]: #method.scopes
+    pub fn add_scope(&mut self, scope: Scope) -> &mut Self {
+        self.scopes.push(scope);
+
+        self
+    }
+
+    /// Add a scope to the scopes for the client credentials grant request.
+    ///
+    /// This is the by-value form of [`add_scope`], for building in one
+    /// expression.
+    ///
+    /// [`add_scope`]: #method.add_scope
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.add_scope(scope);
+
+        self
     }
 
     /// Set the scopes for the client credentials grant request.
     ///
-    /// By default the [`Identify`] scope is selected.
+    /// By default the client's [default scopes] are selected, or the
+    /// [`Identify`] scope if the client has none.
+    ///
+    /// Scopes or references to scopes can be passed from any iterator, such
+    /// as an array, a slice, or an iterator chain. They're copied into a
+    /// vector owned by the builder.
     ///
     /// Read about Discord's [scope documentation].
     ///
@@ -143,9 +424,23 @@ impl<'a> ClientCredentialsGrantBuilder<'a> {
     /// [`Bot`]: enum.Scope.html#variant.Bot
     /// [`Identify`]: enum.Scope.html#variant.Identify
     /// [RFC 6749 § 3.3]: https://tools.ietf.org/html/rfc6749#section-3.3
+    /// [default scopes]: ../../client/struct.Client.html#method.with_default_scopes
     /// [scope documentation]: https://discord.com/developers/docs/topics/oauth2#shared-resources-oauth2-scopes
-    pub fn scopes(&mut self, scopes: &'a [Scope]) -> &mut Self {
-        self.scopes = scopes;
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes = scopes.into_iter().map(|scope| *scope.borrow()).collect();
+
+        self
+    }
+
+    /// Set the scopes for the client credentials grant request.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
 
         self
     }
@@ -155,12 +450,20 @@ impl<'a> ClientCredentialsGrantBuilder<'a> {
 mod tests {
     use super::{
         Client, ClientCredentialsGrantBuilder, ClientCredentialsGrantRequest,
-        ClientCredentialsGrantRequestBody, ClientCredentialsGrantResponse, GrantType, Scope,
+        ClientCredentialsGrantRequestBody, ClientCredentialsGrantResponse,
+        ClientCredentialsGrantResponseRef, GrantType, Scope,
+    };
+    use crate::id::ApplicationId;
+    use crate::{
+        fixtures,
+        request::{Method, USER_AGENT},
     };
     use serde::{Deserialize, Serialize};
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::fmt::Debug;
-    use twilight_model::id::ApplicationId;
+    use std::{
+        borrow::Cow,
+        fmt::{Debug, Display},
+    };
 
     assert_fields!(ClientCredentialsGrantRequestBody<'_>: client_id, client_secret, grant_type, scope);
     assert_fields!(ClientCredentialsGrantRequest<'_>: body, headers, url_base);
@@ -171,7 +474,17 @@ mod tests {
         scope
     );
     assert_impl_all!(ClientCredentialsGrantBuilder<'_>: Clone, Debug, Send, Sync);
-    assert_impl_all!(ClientCredentialsGrantRequestBody<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(
+        ClientCredentialsGrantRequestBody<'static>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Display,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
     assert_impl_all!(ClientCredentialsGrantRequest<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
     assert_impl_all!(
         ClientCredentialsGrantResponse: Clone,
@@ -183,24 +496,64 @@ mod tests {
         Serialize,
         Sync
     );
+    assert_fields!(
+        ClientCredentialsGrantResponseRef<'_>: access_token,
+        expires_in,
+        token_type,
+        scope
+    );
+    assert_impl_all!(
+        ClientCredentialsGrantResponseRef<'_>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_response_ref() {
+        let response: ClientCredentialsGrantResponseRef<'_> =
+            serde_json::from_slice(fixtures::CLIENT_CREDENTIALS_GRANT_RESPONSE).unwrap();
+        assert!(matches!(response.access_token, Cow::Borrowed(_)));
+
+        let debug = format!("{response:?}");
+        assert!(!debug.contains("6qrZcUqja7812RVdnEKjpzOL4CvHBFG"));
+
+        assert_eq!(
+            fixtures::client_credentials_grant_response(),
+            response.into_owned()
+        );
+    }
 
     #[test]
     fn test_client_credentials_grant_request() {
         let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
         let mut builder = client.client_credentials_grant();
         let req = builder.build();
+        assert_eq!(Method::Post, req.method());
         assert_eq!(
             req.headers,
-            &[("Content-Type", "application/x-www-form-urlencoded")]
+            [
+                (
+                    "Content-Type".into(),
+                    "application/x-www-form-urlencoded".into()
+                ),
+                ("User-Agent".into(), USER_AGENT.into()),
+                ("Authorization".into(), "Basic MTph".into()),
+            ]
+            .as_ref()
         );
         assert_eq!(req.url_base, "https://discord.com/api/v6/oauth2/token");
         assert_eq!(
             req.body,
             ClientCredentialsGrantRequestBody {
                 client_id: ApplicationId(1),
-                client_secret: "a",
+                client_secret: Cow::Borrowed("a"),
                 grant_type: GrantType::ClientCredentials,
-                scope: Scope::Identify.name().to_owned(),
+                scope: Cow::Borrowed(Scope::Identify.name()),
             }
         );
         assert_eq!(
@@ -208,15 +561,15 @@ mod tests {
             req.url(),
         );
 
-        builder.scopes(&[Scope::Guilds, Scope::Identify]);
+        builder.scopes([Scope::Guilds, Scope::Identify]);
         let req = builder.build();
         assert_eq!(
             req.body,
             ClientCredentialsGrantRequestBody {
                 client_id: ApplicationId(1),
-                client_secret: "a",
+                client_secret: Cow::Borrowed("a"),
                 grant_type: GrantType::ClientCredentials,
-                scope: "guilds identify".to_owned(),
+                scope: Cow::Owned("guilds identify".to_owned()),
             }
         );
         assert_eq!(
@@ -224,4 +577,128 @@ mod tests {
             req.url(),
         );
     }
+
+    #[test]
+    fn test_body_scope_cow() {
+        let borrowed = ClientCredentialsGrantRequestBody {
+            client_id: ApplicationId(1),
+            client_secret: Cow::Borrowed("a"),
+            grant_type: GrantType::ClientCredentials,
+            scope: Cow::Borrowed("identify"),
+        };
+        let owned = ClientCredentialsGrantRequestBody {
+            scope: Cow::Owned("identify".to_owned()),
+            ..borrowed.clone()
+        };
+
+        assert_eq!(borrowed, owned);
+        assert_eq!(
+            serde_json::to_string(&borrowed).unwrap(),
+            serde_json::to_string(&owned).unwrap()
+        );
+        assert_eq!(
+            r#"{"client_id":"1","client_secret":"a","grant_type":"client_credentials","scope":"identify"}"#,
+            serde_json::to_string(&borrowed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_url() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client.client_credentials_grant();
+        let mut buf = "existing".to_owned();
+
+        let first = builder.build();
+        first.write_url(&mut buf);
+        builder.scopes([Scope::Guilds, Scope::Identify]);
+        let second = builder.build();
+        second.write_url(&mut buf);
+
+        assert_eq!(format!("existing{}{}", first.url(), second.url()), buf);
+    }
+
+    #[test]
+    fn test_client_credentials_grant_default_scopes() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"])
+            .unwrap()
+            .with_default_scopes(vec![Scope::Connections]);
+        let mut builder = client.client_credentials_grant();
+        assert_eq!("connections", builder.build().body.scope);
+
+        builder.add_scope(Scope::Guilds);
+        assert_eq!("connections guilds", builder.build().body.scope);
+
+        builder.scopes([Scope::Identify]);
+        assert_eq!("identify", builder.build().body.scope);
+
+        builder.add_scope(Scope::Email);
+        assert_eq!("identify email", builder.build().body.scope);
+    }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+
+        let mut builder = client.client_credentials_grant();
+        builder
+            .scopes([Scope::Identify])
+            .add_scope(Scope::Connections);
+        let fluent = client
+            .client_credentials_grant()
+            .with_scopes([Scope::Identify])
+            .with_scope(Scope::Connections);
+
+        assert_eq!(builder.build(), fluent.build());
+    }
+
+    #[test]
+    fn test_body_display() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client.client_credentials_grant().build();
+        let display = request.body.to_string();
+
+        assert_eq!(
+            "client_id=1&client_secret=<redacted>&grant_type=client_credentials&scope=identify",
+            display
+        );
+        assert!(!display.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client.client_credentials_grant().build();
+        let curl = request.to_curl(false);
+
+        assert_eq!(
+            format!(
+                "curl -X POST 'https://discord.com/api/v6/oauth2/token' \\\n  \
+                -H 'Content-Type: application/x-www-form-urlencoded' \\\n  \
+                -H 'User-Agent: {USER_AGENT}' \\\n  \
+                -u \"1:$CLIENT_SECRET\" \\\n  \
+                --data-urlencode 'grant_type=client_credentials' \\\n  \
+                --data-urlencode 'scope=identify'"
+            ),
+            curl
+        );
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains(&client.basic_authorization()));
+
+        let curl = request.to_curl(true);
+        assert!(curl.contains("-u '1:hunter2'"));
+    }
+
+    #[test]
+    fn test_body_round_trip() {
+        let client = Client::new(ApplicationId(1), "hunter 2&", &["https://example.com"]).unwrap();
+        let request = client
+            .client_credentials_grant()
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+
+        let json = serde_json::to_string(&request.body).unwrap();
+        let from_json =
+            serde_json::from_str::<ClientCredentialsGrantRequestBody<'_>>(&json).unwrap();
+        assert_eq!(request.body, from_json);
+    }
 }
@@ -0,0 +1,354 @@
+//! Parse the response of any authorization code exchange.
+//!
+//! Which fields Discord includes in the response of an exchange depends on
+//! the scopes that were authorized: the [`WebhookIncoming`] scope adds a
+//! webhook and the [`Bot`] scope adds a guild. When one callback handles
+//! codes of several flows, parse responses as an [`ExchangeResponse`] and
+//! check which of them are present.
+//!
+//! This module is only available with the `model` feature enabled.
+//!
+//! [`Bot`]: ../../enum.Scope.html#variant.Bot
+//! [`ExchangeResponse`]: struct.ExchangeResponse.html
+//! [`WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
+
+use super::{
+    super::{
+        bot_authorization::BotAuthorization, AccessToken, RefreshToken, TokenResponse, TokenType,
+    },
+    access_token_exchange::AccessTokenExchangeResponse,
+    webhook_token_exchange::{RedactedWebhook, WebhookTokenExchangeResponse},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use twilight_model::{channel::Webhook, guild::PartialGuild};
+
+/// Response from exchanging an authorization code of any flow.
+///
+/// This is a superset of the other exchange responses, so a response with a
+/// webhook, a guild, both, or neither deserializes without losing fields.
+/// The token is available via the [`TokenResponse`] methods.
+///
+/// The tokens, including the token of the webhook, are redacted from the
+/// `Debug` output.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::{request::exchange_response::ExchangeResponse, TokenResponse};
+///
+/// let response = serde_json::from_str::<ExchangeResponse>(r#"{
+///     "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+///     "token_type": "Bearer",
+///     "expires_in": 604800,
+///     "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+///     "scope": "identify"
+/// }"#)?;
+///
+/// assert_eq!("identify", response.scope());
+/// assert!(response.guild().is_none());
+/// assert!(response.webhook().is_none());
+/// # Ok::<_, serde_json::Error>(())
+/// ```
+///
+/// [`TokenResponse`]: ../../trait.TokenResponse.html
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ExchangeResponse {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    pub access_token: AccessToken,
+    /// Number of seconds from issuing that the access token is valid.
+    ///
+    /// After this duration, the refresh token must be exchanged for another
+    /// access token and refresh token pair.
+    pub expires_in: u64,
+    /// Guild that a bot was added to.
+    ///
+    /// This is present if the [`Bot`] scope was authorized with a redirect
+    /// URI.
+    ///
+    /// [`Bot`]: ../../enum.Scope.html#variant.Bot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild: Option<PartialGuild>,
+    /// Refresh token to use to exchange for another access token and refresh
+    /// token pair.
+    pub refresh_token: RefreshToken,
+    /// Space-delimited list of scopes that the token has had approved.
+    pub scope: String,
+    /// Type of token provided.
+    ///
+    /// This will always be [`TokenType::Bearer`].
+    ///
+    /// [`TokenType::Bearer`]: ../../enum.TokenType.html#variant.Bearer
+    pub token_type: TokenType,
+    /// Webhook that the user created via authorization.
+    ///
+    /// This is present if the [`WebhookIncoming`] scope was authorized.
+    ///
+    /// [`WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<Webhook>,
+}
+
+impl ExchangeResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    #[cfg(feature = "json")]
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+
+    /// Return the guild that a bot was added to, if any.
+    pub const fn guild(&self) -> Option<&PartialGuild> {
+        self.guild.as_ref()
+    }
+
+    /// Return the webhook that the user created, if any.
+    pub const fn webhook(&self) -> Option<&Webhook> {
+        self.webhook.as_ref()
+    }
+
+    /// Convert into the response of a bot authorization.
+    ///
+    /// Returns `None` if the response has no guild; check [`guild`] first
+    /// to keep the response in that case.
+    ///
+    /// [`guild`]: #method.guild
+    pub fn into_bot_authorization(self) -> Option<BotAuthorization> {
+        let guild = self.guild?;
+
+        Some(BotAuthorization {
+            access_token: self.access_token,
+            expires_in: self.expires_in,
+            guild,
+            refresh_token: self.refresh_token,
+            scope: self.scope,
+            token_type: self.token_type,
+        })
+    }
+
+    /// Convert into the response of a webhook token exchange.
+    ///
+    /// Returns `None` if the response has no webhook; check [`webhook`] first
+    /// to keep the response in that case.
+    ///
+    /// [`webhook`]: #method.webhook
+    pub fn into_webhook_response(self) -> Option<WebhookTokenExchangeResponse> {
+        let webhook = self.webhook?;
+
+        Some(WebhookTokenExchangeResponse {
+            access_token: self.access_token,
+            expires_in: self.expires_in,
+            refresh_token: self.refresh_token,
+            scope: self.scope,
+            token_type: self.token_type,
+            webhook,
+        })
+    }
+}
+
+impl Debug for ExchangeResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ExchangeResponse")
+            .field("access_token", &self.access_token)
+            .field("expires_in", &self.expires_in)
+            .field("guild", &self.guild)
+            .field("refresh_token", &self.refresh_token)
+            .field("scope", &self.scope)
+            .field("token_type", &self.token_type)
+            .field("webhook", &self.webhook.as_ref().map(RedactedWebhook))
+            .finish()
+    }
+}
+
+impl From<AccessTokenExchangeResponse> for ExchangeResponse {
+    fn from(response: AccessTokenExchangeResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_in: response.expires_in,
+            guild: None,
+            refresh_token: response.refresh_token,
+            scope: response.scope,
+            token_type: response.token_type,
+            webhook: response.webhook,
+        }
+    }
+}
+
+impl From<BotAuthorization> for ExchangeResponse {
+    fn from(response: BotAuthorization) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_in: response.expires_in,
+            guild: Some(response.guild),
+            refresh_token: response.refresh_token,
+            scope: response.scope,
+            token_type: response.token_type,
+            webhook: None,
+        }
+    }
+}
+
+impl From<WebhookTokenExchangeResponse> for ExchangeResponse {
+    fn from(response: WebhookTokenExchangeResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_in: response.expires_in,
+            guild: None,
+            refresh_token: response.refresh_token,
+            scope: response.scope,
+            token_type: response.token_type,
+            webhook: Some(response.webhook),
+        }
+    }
+}
+
+impl TokenResponse for ExchangeResponse {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        Some(&self.refresh_token)
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExchangeResponse;
+    use crate::{
+        bot_authorization::BotAuthorization,
+        fixtures::{
+            self, ACCESS_TOKEN_RESPONSE, BOT_AUTHORIZATION_RESPONSE, WEBHOOK_TOKEN_RESPONSE,
+        },
+        request::{
+            access_token_exchange::AccessTokenExchangeResponse,
+            webhook_token_exchange::WebhookTokenExchangeResponse,
+        },
+        TokenResponse,
+    };
+    use serde::{Deserialize, Serialize};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::fmt::Debug;
+
+    assert_fields!(
+        ExchangeResponse: access_token,
+        expires_in,
+        guild,
+        refresh_token,
+        scope,
+        token_type,
+        webhook
+    );
+    assert_impl_all!(
+        ExchangeResponse: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        From<AccessTokenExchangeResponse>,
+        From<BotAuthorization>,
+        From<WebhookTokenExchangeResponse>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+        TokenResponse
+    );
+
+    #[test]
+    fn test_token_response() {
+        let response = serde_json::from_slice::<ExchangeResponse>(ACCESS_TOKEN_RESPONSE).unwrap();
+        assert_eq!(
+            ExchangeResponse::from(fixtures::access_token_response()),
+            response
+        );
+        assert_eq!(
+            "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            response.access_token().expose()
+        );
+        assert!(response.guild().is_none());
+        assert!(response.webhook().is_none());
+
+        assert!(response.clone().into_bot_authorization().is_none());
+        assert!(response.into_webhook_response().is_none());
+    }
+
+    #[test]
+    fn test_webhook_response() {
+        let response = serde_json::from_slice::<ExchangeResponse>(WEBHOOK_TOKEN_RESPONSE).unwrap();
+        assert_eq!(
+            ExchangeResponse::from(fixtures::webhook_token_response()),
+            response
+        );
+        assert_eq!(
+            Some("testwebhook"),
+            response
+                .webhook()
+                .and_then(|webhook| webhook.name.as_deref())
+        );
+        assert!(response.guild().is_none());
+        assert_eq!(
+            fixtures::webhook_token_response(),
+            response.into_webhook_response().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bot_response() {
+        let response =
+            serde_json::from_slice::<ExchangeResponse>(BOT_AUTHORIZATION_RESPONSE).unwrap();
+        assert_eq!(
+            ExchangeResponse::from(fixtures::bot_authorization_response()),
+            response
+        );
+        assert_eq!(
+            Some("test guild"),
+            response.guild().map(|guild| guild.name.as_str())
+        );
+        assert!(response.webhook().is_none());
+        assert_eq!(
+            fixtures::bot_authorization_response(),
+            response.into_bot_authorization().unwrap()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_from_json_bytes() {
+        for body in &[
+            ACCESS_TOKEN_RESPONSE,
+            BOT_AUTHORIZATION_RESPONSE,
+            WEBHOOK_TOKEN_RESPONSE,
+        ] {
+            assert_eq!(
+                serde_json::from_slice::<ExchangeResponse>(body).unwrap(),
+                ExchangeResponse::from_json_bytes(body).unwrap()
+            );
+        }
+    }
+}
@@ -1,4 +1,374 @@
+//! Requests to Discord's token endpoint and their responses.
+
 pub mod access_token_exchange;
 pub mod client_credentials_grant;
+#[cfg(feature = "model")]
+pub mod exchange_response;
 pub mod refresh_token_exchange;
+pub mod token_introspection;
+pub mod token_request;
+pub mod token_revocation;
+#[cfg(feature = "model")]
 pub mod webhook_token_exchange;
+
+use std::{borrow::Cow, fmt::Write};
+
+/// Value of the `User-Agent` header sent with requests.
+///
+/// Discord asks that libraries identify themselves with this format.
+pub const USER_AGENT: &str = concat!(
+    "DiscordBot (https://github.com/twilight-rs/oauth2, ",
+    env!("CARGO_PKG_VERSION"),
+    ")",
+);
+
+/// Name and value of a header.
+pub type Header = (Cow<'static, str>, Cow<'static, str>);
+
+/// Headers of a request.
+///
+/// The default headers are borrowed, so requests only allocate for headers
+/// when they're customized. Use [`Cow::to_mut`] to add or change headers of
+/// a built request:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{id::ApplicationId, Client};
+///
+/// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+/// let mut request = client.client_credentials_grant().build();
+/// request
+///     .headers
+///     .to_mut()
+///     .push(("X-Trace-Id".into(), "abc123".into()));
+///
+/// assert_eq!(4, request.headers.len());
+/// # Ok(()) }
+/// ```
+///
+/// [`Cow::to_mut`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.to_mut
+pub type Headers = Cow<'static, [Header]>;
+
+/// Name of the `Content-Type` header.
+pub(crate) const CONTENT_TYPE: &str = "Content-Type";
+
+/// Value of the `Content-Type` header sent with every request.
+pub(crate) const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Headers sent with every request to the token endpoint.
+pub(crate) const HEADERS: &[Header] = &[
+    (
+        Cow::Borrowed(CONTENT_TYPE),
+        Cow::Borrowed(FORM_CONTENT_TYPE),
+    ),
+    (Cow::Borrowed("User-Agent"), Cow::Borrowed(USER_AGENT)),
+];
+
+/// Value of a form field in a curl command.
+pub(crate) enum CurlValue<'a> {
+    /// Value rendered as is.
+    Plain(&'a str),
+    /// Sensitive value, rendered as a reference to the named shell variable
+    /// unless unredacted.
+    Secret(&'a str, &'static str),
+}
+
+/// Render a request as a curl command, with each argument on its own line.
+///
+/// Arguments are single-quoted for POSIX shells. Redacted secrets are
+/// double-quoted so that the shell expands their variable. If `basic_auth`
+/// is set, its user and password are passed via `-u`, which replaces the
+/// `Authorization` header so that it isn't rendered.
+pub(crate) fn curl(
+    method: Method,
+    url: &str,
+    headers: &[Header],
+    basic_auth: Option<(&str, CurlValue<'_>)>,
+    fields: &[(&str, CurlValue<'_>)],
+    unredacted: bool,
+) -> String {
+    let mut command = String::from("curl -X ");
+    command.push_str(method.name());
+    command.push(' ');
+    push_shell_quoted(&mut command, url);
+
+    for (name, value) in headers {
+        if basic_auth.is_some() && name.eq_ignore_ascii_case("Authorization") {
+            continue;
+        }
+
+        command.push_str(" \\\n  -H ");
+        push_shell_quoted(&mut command, &format!("{name}: {value}"));
+    }
+
+    if let Some((user, password)) = basic_auth {
+        command.push_str(" \\\n  -u ");
+        push_curl_value(&mut command, user, ':', &password, unredacted);
+    }
+
+    for (name, value) in fields {
+        command.push_str(" \\\n  --data-urlencode ");
+        push_curl_value(&mut command, name, '=', value, unredacted);
+    }
+
+    command
+}
+
+/// Push a name and value pair of a curl command, separated by a delimiter.
+fn push_curl_value(
+    buf: &mut String,
+    name: &str,
+    delimiter: char,
+    value: &CurlValue<'_>,
+    unredacted: bool,
+) {
+    match value {
+        CurlValue::Secret(_, variable) if !unredacted => {
+            let _ = write!(buf, "\"{name}{delimiter}${variable}\"");
+        }
+        CurlValue::Plain(value) | CurlValue::Secret(value, _) => {
+            push_shell_quoted(buf, &format!("{name}{delimiter}{value}"));
+        }
+    }
+}
+
+/// Push a value single-quoted for POSIX shells.
+fn push_shell_quoted(buf: &mut String, value: &str) {
+    buf.push('\'');
+
+    for (idx, part) in value.split('\'').enumerate() {
+        if idx > 0 {
+            buf.push_str("'\\''");
+        }
+
+        buf.push_str(part);
+    }
+
+    buf.push('\'');
+}
+
+/// HTTP method of a request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Method {
+    /// GET method.
+    Get,
+    /// POST method.
+    Post,
+}
+
+impl Method {
+    /// Return the name of the method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::request::Method;
+    ///
+    /// assert_eq!("POST", Method::Post.name());
+    /// ```
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+        }
+    }
+}
+
+/// Request that can be performed by any HTTP client.
+///
+/// This is implemented by every request, so code performing requests can be
+/// generic over them.
+///
+/// Contrasted from the `url` method on some requests, the [`endpoint`]
+/// doesn't include the parameters, which are instead in the urlencoded
+/// [`body`]. It therefore never contains the client secret, so it can be
+/// logged.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{id::ApplicationId, request::OAuthRequest, Client};
+///
+/// fn describe(request: &impl OAuthRequest) -> String {
+///     format!("{} {}", request.method().name(), request.endpoint())
+/// }
+///
+/// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+/// let request = client.client_credentials_grant().build();
+///
+/// assert_eq!("POST https://discord.com/api/v6/oauth2/token", describe(&request));
+/// # Ok(()) }
+/// ```
+///
+/// [`body`]: #tymethod.body
+/// [`endpoint`]: #tymethod.endpoint
+pub trait OAuthRequest {
+    /// Return the HTTP method of the request.
+    fn method(&self) -> Method;
+
+    /// Return the URL to send the request to, without parameters.
+    fn endpoint(&self) -> Cow<'_, str>;
+
+    /// Return the headers to send.
+    ///
+    /// This includes the [`USER_AGENT`], and the `Authorization` header for
+    /// requests authenticating the client via HTTP Basic authentication.
+    /// Headers must therefore be redacted before they are logged.
+    ///
+    /// [`USER_AGENT`]: constant.USER_AGENT.html
+    fn headers(&self) -> &[Header];
+
+    /// Return the urlencoded body to send, if any.
+    fn body(&self) -> Option<String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurlValue, Method, OAuthRequest, USER_AGENT};
+    use crate::id::ApplicationId;
+    use crate::{Client, StoredToken};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_impl_all!(Method: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    /// Convert a urlencoded form into a JSON object of its fields, so request
+    /// bodies can be deserialized from their form.
+    pub(super) fn form_to_json(form: &str) -> String {
+        let fields = url::form_urlencoded::parse(form.as_bytes())
+            .into_owned()
+            .map(|(key, value)| (key, serde_json::Value::String(value)))
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::to_string(&fields).unwrap()
+    }
+
+    /// Perform a request through the trait, returning what an HTTP client
+    /// would send.
+    fn send(request: &impl OAuthRequest) -> (String, String, Vec<String>, Option<String>) {
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+
+        (
+            request.method().name().to_owned(),
+            request.endpoint().into_owned(),
+            headers,
+            request.body(),
+        )
+    }
+
+    #[test]
+    fn test_oauth_request() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let url = "https://discord.com/api/v6/oauth2/token".to_owned();
+        let headers = vec![
+            "Content-Type: application/x-www-form-urlencoded".to_owned(),
+            format!("User-Agent: {USER_AGENT}"),
+        ];
+
+        let builder = client
+            .access_token_exchange("b", "https://example.com")
+            .unwrap();
+        assert_eq!(
+            (
+                "POST".to_owned(),
+                url.clone(),
+                headers.clone(),
+                Some(
+                    "client_id=1&client_secret=a&code=b\
+                    &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fexample.com%2F&scope="
+                        .to_owned()
+                ),
+            ),
+            send(&builder.build())
+        );
+
+        // The client credentials grant authenticates via HTTP Basic
+        // authentication rather than its body.
+        let mut basic_auth_headers = headers.clone();
+        basic_auth_headers.push("Authorization: Basic MTph".to_owned());
+        let builder = client.client_credentials_grant();
+        assert_eq!(
+            (
+                "POST".to_owned(),
+                url.clone(),
+                basic_auth_headers,
+                Some("grant_type=client_credentials&scope=identify".to_owned()),
+            ),
+            send(&builder.build())
+        );
+
+        let token = StoredToken::new("c", 1_000, Some("d".to_owned()), "identify");
+        assert_eq!(
+            (
+                "POST".to_owned(),
+                url,
+                headers,
+                Some(
+                    "client_id=1&client_secret=a\
+                    &grant_type=refresh_token&redirect_uri=https%3A%2F%2Fexample.com%2F&refresh_token=d"
+                        .to_owned()
+                ),
+            ),
+            send(&token.refresh_request(&client).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_user_agent() {
+        assert!(USER_AGENT.starts_with("DiscordBot (https://github.com/twilight-rs/oauth2, "));
+        assert!(USER_AGENT.contains(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_headers_serde() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let request = client.client_credentials_grant().build();
+
+        assert_eq!(
+            serde_json::json!([
+                ["Content-Type", "application/x-www-form-urlencoded"],
+                ["User-Agent", USER_AGENT],
+                ["Authorization", "Basic MTph"],
+            ]),
+            serde_json::to_value(&request.headers).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_curl_quoting() {
+        let command = super::curl(
+            Method::Post,
+            "https://example.com/it's",
+            &[],
+            None,
+            &[
+                ("state", CurlValue::Plain("a'b c")),
+                ("secret", CurlValue::Secret("x'y", "SECRET")),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            "curl -X POST 'https://example.com/it'\\''s' \\\n  \
+            --data-urlencode 'state=a'\\''b c' \\\n  \
+            --data-urlencode \"secret=$SECRET\"",
+            command
+        );
+
+        let command = super::curl(
+            Method::Post,
+            "https://example.com",
+            &[],
+            None,
+            &[("secret", CurlValue::Secret("x'y", "SECRET"))],
+            true,
+        );
+        assert!(command.ends_with("--data-urlencode 'secret=x'\\''y'"));
+    }
+}
@@ -1,28 +1,67 @@
 use super::super::{
+    form::{self, FormWriter},
     scope::{self, Scope},
-    Client, GrantType, TokenType,
+    AccessToken, Client, GrantType, RefreshToken, TokenResponse, TokenType,
 };
+use super::{CurlValue, Header, Headers, Method, OAuthRequest};
+use crate::id::{self, ApplicationId};
 use serde::{Deserialize, Serialize};
-use std::fmt::Write;
-use twilight_model::id::ApplicationId;
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+/// Body of a refresh token exchange request.
+///
+/// The `Display` implementation renders the fields like the form that is
+/// sent, unencoded and with the client secret and refresh token replaced by
+/// `<redacted>`, so that it can be logged.
+///
+/// Bodies deserialize from the fields they serialize to, so that a proxy can
+/// read a request it received and forward it. Strings are borrowed from the
+/// input where possible, and the client ID may be a string or an integer.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct RefreshTokenExchangeRequestBody<'a> {
     /// ID of the application that was authorized.
+    #[serde(deserialize_with = "id::deserialize_id")]
     pub client_id: ApplicationId,
     /// Secret of the application that was authorized.
-    pub client_secret: &'a str,
+    #[serde(borrow)]
+    pub client_secret: Cow<'a, str>,
     /// Type of grant approval.
     pub grant_type: GrantType,
     /// Redirect URi that the user was redirected to.
-    pub redirect_uri: &'a str,
+    #[serde(borrow)]
+    pub redirect_uri: Cow<'a, str>,
     /// Refresh token provided in the access token exchange response.
-    pub refresh_token: &'a str,
-    /// List of scopes that the user granted.
+    #[serde(borrow)]
+    pub refresh_token: Cow<'a, str>,
+    /// List of scopes to narrow the refreshed access token down to.
     ///
-    /// This is space-delimited.
-    pub scope: String,
+    /// This is space-delimited. When not present the refreshed access token
+    /// has the same scopes as the original.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Cow<'a, str>>,
+}
+
+impl Display for RefreshTokenExchangeRequestBody<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("client_id=")?;
+        Display::fmt(&self.client_id, f)?;
+        f.write_str("&client_secret=<redacted>&grant_type=")?;
+        f.write_str(self.grant_type.name())?;
+        f.write_str("&redirect_uri=")?;
+        f.write_str(&self.redirect_uri)?;
+        f.write_str("&refresh_token=<redacted>")?;
+
+        if let Some(scope) = self.scope.as_deref() {
+            f.write_str("&scope=")?;
+            f.write_str(scope)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -31,7 +70,11 @@ pub struct RefreshTokenExchangeRequest<'a> {
     /// Body to send.
     pub body: RefreshTokenExchangeRequestBody<'a>,
     /// Headers to send.
-    pub headers: &'static [(&'static str, &'static str)],
+    ///
+    /// This includes the [`USER_AGENT`].
+    ///
+    /// [`USER_AGENT`]: ../constant.USER_AGENT.html
+    pub headers: Headers,
     /// Base of the URL.
     ///
     /// Use the [`url`] method for the full URL with query parameters.
@@ -41,6 +84,15 @@ pub struct RefreshTokenExchangeRequest<'a> {
 }
 
 impl RefreshTokenExchangeRequest<'_> {
+    /// Return the HTTP method to send the request with.
+    ///
+    /// This is always [`Method::Post`].
+    ///
+    /// [`Method::Post`]: ../enum.Method.html#variant.Post
+    pub const fn method(&self) -> Method {
+        Method::Post
+    }
+
     /// Retrieve a URL with the body urlencoded as query parameters.
     ///
     /// This URL can be used to make a POST request with the specified
@@ -48,20 +100,103 @@ impl RefreshTokenExchangeRequest<'_> {
     ///
     /// [`headers`]: #structfield.url_base
     pub fn url(&self) -> String {
-        let mut buf = self.url_base.to_owned();
-        buf.push_str("?client_id=");
-        let _ = write!(buf, "{}", self.body.client_id.0);
-        buf.push_str("&client_secret=");
-        buf.push_str(self.body.client_secret);
-        buf.push_str("&redirect_uri=");
-        buf.push_str(self.body.redirect_uri);
-        buf.push_str("&refresh_token=");
-        buf.push_str(self.body.refresh_token);
-        buf.push_str("&scope=");
-        buf.push_str(&urlencoding::encode(&self.body.scope));
+        let mut buf = String::new();
+        self.write_url(&mut buf);
 
         buf
     }
+
+    /// Append the URL returned by [`url`] to a buffer.
+    ///
+    /// Existing contents of the buffer are left untouched. This allows
+    /// reusing one buffer when building many URLs.
+    ///
+    /// [`url`]: #method.url
+    pub fn write_url(&self, buf: &mut String) {
+        form::append(buf, |form| {
+            form.push_raw(self.url_base);
+            form.push_raw("?");
+            self.write_form(form);
+        });
+    }
+
+    /// Render the request as a curl command, for debugging.
+    ///
+    /// Each body field is passed via `--data-urlencode`, with arguments
+    /// quoted for POSIX shells. Unless `unredacted` is set, the client secret
+    /// and refresh token are replaced by the shell variables
+    /// `$CLIENT_SECRET` and `$REFRESH_TOKEN`, so the command can be shared
+    /// without leaking them.
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        let client_id = self.body.client_id.0.to_string();
+        let mut fields = vec![
+            ("client_id", CurlValue::Plain(&client_id)),
+            (
+                "client_secret",
+                CurlValue::Secret(&self.body.client_secret, "CLIENT_SECRET"),
+            ),
+            ("grant_type", CurlValue::Plain(self.body.grant_type.name())),
+            ("redirect_uri", CurlValue::Plain(&self.body.redirect_uri)),
+            (
+                "refresh_token",
+                CurlValue::Secret(&self.body.refresh_token, "REFRESH_TOKEN"),
+            ),
+        ];
+
+        if let Some(scope) = self.body.scope.as_deref() {
+            fields.push(("scope", CurlValue::Plain(scope)));
+        }
+
+        super::curl(
+            self.method(),
+            self.url_base,
+            &self.headers,
+            None,
+            &fields,
+            unredacted,
+        )
+    }
+
+    /// Urlencode the body.
+    fn form(&self) -> String {
+        form::build(|form| self.write_form(form))
+    }
+
+    fn write_form(&self, form: &mut dyn FormWriter) {
+        form.push_raw("client_id=");
+        form.push_number(self.body.client_id.0);
+        form.push_raw("&client_secret=");
+        form.push_encoded(&self.body.client_secret);
+        form.push_raw("&grant_type=");
+        form.push_encoded(self.body.grant_type.name());
+        form.push_raw("&redirect_uri=");
+        form.push_encoded(&self.body.redirect_uri);
+        form.push_raw("&refresh_token=");
+        form.push_encoded(&self.body.refresh_token);
+
+        if let Some(scope) = self.body.scope.as_ref() {
+            form.push_raw("&scope=");
+            form.push_encoded(scope);
+        }
+    }
+}
+
+impl OAuthRequest for RefreshTokenExchangeRequest<'_> {
+    fn method(&self) -> Method {
+        RefreshTokenExchangeRequest::method(self)
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.url_base)
+    }
+
+    fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.form())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -69,7 +204,7 @@ impl RefreshTokenExchangeRequest<'_> {
 pub struct RefreshTokenExchangeResponse {
     /// Access token to be used when making requests to the API on the user's
     /// behalf.
-    pub access_token: String,
+    pub access_token: AccessToken,
     /// Number of seconds from issuing that the access token is valid.
     ///
     /// After this duration, the refresh token must be exchanged for another
@@ -77,7 +212,7 @@ pub struct RefreshTokenExchangeResponse {
     pub expires_in: u64,
     /// Refresh token to use to exchange for another access token and refresh
     /// token pair.
-    pub refresh_token: String,
+    pub refresh_token: RefreshToken,
     /// Space-delimited list of scopes that the token has had approved.
     pub scope: String,
     /// Type of token provided.
@@ -88,11 +223,135 @@ pub struct RefreshTokenExchangeResponse {
     pub token_type: TokenType,
 }
 
+#[cfg(feature = "json")]
+impl RefreshTokenExchangeResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl TokenResponse for RefreshTokenExchangeResponse {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        Some(&self.refresh_token)
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+/// Borrowed form of [`RefreshTokenExchangeResponse`].
+///
+/// Strings borrow from the deserialized input when possible, such as when
+/// parsing a JSON body without escape sequences, and are owned otherwise.
+/// Use [`into_owned`] to convert it into an [`RefreshTokenExchangeResponse`].
+///
+/// Tokens are redacted from the `Debug` output. Unlike [`AccessToken`] and
+/// [`RefreshToken`], borrowed tokens aren't zeroed when dropped.
+///
+/// [`RefreshTokenExchangeResponse`]: struct.RefreshTokenExchangeResponse.html
+/// [`AccessToken`]: ../../struct.AccessToken.html
+/// [`RefreshToken`]: ../../struct.RefreshToken.html
+/// [`into_owned`]: #method.into_owned
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct RefreshTokenExchangeResponseRef<'a> {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    #[serde(borrow)]
+    pub access_token: Cow<'a, str>,
+    /// Number of seconds from issuing that the access token is valid.
+    pub expires_in: u64,
+    /// Refresh token to use to exchange for another access token and refresh
+    /// token pair.
+    #[serde(borrow)]
+    pub refresh_token: Cow<'a, str>,
+    /// Space-delimited list of scopes that the token has had approved.
+    #[serde(borrow)]
+    pub scope: Cow<'a, str>,
+    /// Type of token provided.
+    pub token_type: TokenType,
+}
+
+impl RefreshTokenExchangeResponseRef<'_> {
+    /// Convert into an owned response, allocating any borrowed strings.
+    pub fn into_owned(self) -> RefreshTokenExchangeResponse {
+        RefreshTokenExchangeResponse {
+            access_token: AccessToken::new(self.access_token),
+            expires_in: self.expires_in,
+            refresh_token: RefreshToken::new(self.refresh_token),
+            scope: self.scope.into_owned(),
+            token_type: self.token_type,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> RefreshTokenExchangeResponseRef<'a> {
+    /// Parse a response from a JSON body, borrowing strings from it when
+    /// possible.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &'a [u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl Debug for RefreshTokenExchangeResponseRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("RefreshTokenExchangeResponseRef");
+        debug.field("access_token", &format_args!("<redacted>"));
+        debug.field("expires_in", &self.expires_in);
+        debug.field("refresh_token", &format_args!("<redacted>"));
+        debug.field("scope", &self.scope);
+        debug.field("token_type", &self.token_type);
+
+        debug.finish()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RefreshTokenExchangeBuilder<'a> {
     client: &'a Client,
     refresh_token: &'a str,
-    scopes: Option<&'a [Scope]>,
+    scopes: Option<Vec<Scope>>,
 }
 
 impl<'a> RefreshTokenExchangeBuilder<'a> {
@@ -106,28 +365,77 @@ impl<'a> RefreshTokenExchangeBuilder<'a> {
         }
     }
 
-    pub fn build(&'a self) -> RefreshTokenExchangeRequest<'a> {
-        RefreshTokenExchangeRequest {
+    /// Build a refresh token exchange request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client has no configured redirect URIs.
+    pub fn build(&self) -> RefreshTokenExchangeRequest<'a> {
+        let scope = self.scopes.as_deref().map(scope::join_cow);
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "oauth2.refresh",
+            application_id = self.client.client_id().0,
+            grant_type = GrantType::RefreshToken.name(),
+            scopes = scope.as_deref().unwrap_or_default(),
+        )
+        .entered();
+        let request = RefreshTokenExchangeRequest {
             body: RefreshTokenExchangeRequestBody {
                 client_id: self.client.client_id(),
-                client_secret: self.client.client_secret(),
+                client_secret: Cow::Borrowed(self.client.client_secret()),
                 grant_type: GrantType::RefreshToken,
-                redirect_uri: self
-                    .client
-                    .redirect_uris()
-                    .first()
-                    .expect("redirect uri must be configured")
-                    .as_ref(),
-                refresh_token: self.refresh_token,
-                scope: self.scopes.map(scope::join).unwrap_or_default(),
+                redirect_uri: Cow::Borrowed(
+                    self.client.redirect_uri_text(
+                        self.client
+                            .redirect_uris()
+                            .first()
+                            .expect("redirect uri must be configured"),
+                    ),
+                ),
+                refresh_token: Cow::Borrowed(self.refresh_token),
+                scope,
             },
-            headers: &[("Content-Type", "application/x-www-form-urlencoded")],
+            headers: self.client.headers(),
             url_base: Self::BASE_URL,
-        }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(body = %request.body, "built refresh token exchange request");
+
+        request
+    }
+
+    /// Set the scopes to narrow the refreshed access token down to.
+    ///
+    /// The scopes must be a subset of the scopes originally granted. When not
+    /// set, the `scope` parameter is omitted and the refreshed access token
+    /// has the same scopes as the original.
+    ///
+    /// Scopes or references to scopes can be passed from any iterator, such
+    /// as an array, a slice, or an iterator chain. They're copied into a
+    /// vector owned by the builder.
+    ///
+    /// Refer to [RFC 6749 § 6] for more information.
+    ///
+    /// [RFC 6749 § 6]: https://tools.ietf.org/html/rfc6749#section-6
+    pub fn scopes<T: Borrow<Scope>>(&mut self, scopes: impl IntoIterator<Item = T>) -> &mut Self {
+        self.scopes
+            .replace(scopes.into_iter().map(|scope| *scope.borrow()).collect());
+
+        self
     }
 
-    pub fn scopes(&mut self, scopes: &'a [Scope]) -> &mut Self {
-        self.scopes.replace(scopes);
+    /// Set the scopes to narrow the refreshed access token down to.
+    ///
+    /// This is the by-value form of [`scopes`], for building in one
+    /// expression.
+    ///
+    /// [`scopes`]: #method.scopes
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_scopes<T: Borrow<Scope>>(mut self, scopes: impl IntoIterator<Item = T>) -> Self {
+        self.scopes(scopes);
 
         self
     }
@@ -137,12 +445,20 @@ impl<'a> RefreshTokenExchangeBuilder<'a> {
 mod tests {
     use super::{
         Client, GrantType, RefreshTokenExchangeBuilder, RefreshTokenExchangeRequest,
-        RefreshTokenExchangeRequestBody, RefreshTokenExchangeResponse, Scope,
+        RefreshTokenExchangeRequestBody, RefreshTokenExchangeResponse,
+        RefreshTokenExchangeResponseRef, Scope,
+    };
+    use crate::id::ApplicationId;
+    use crate::{
+        fixtures,
+        request::{Method, OAuthRequest, USER_AGENT},
     };
     use serde::{Deserialize, Serialize};
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::fmt::Debug;
-    use twilight_model::id::ApplicationId;
+    use std::{
+        borrow::Cow,
+        fmt::{Debug, Display},
+    };
 
     assert_fields!(RefreshTokenExchangeRequestBody<'_>: client_id, client_secret, grant_type, redirect_uri, refresh_token, scope);
     assert_fields!(RefreshTokenExchangeRequest<'_>: body, headers, url_base);
@@ -154,7 +470,17 @@ mod tests {
         token_type
     );
     assert_impl_all!(RefreshTokenExchangeBuilder<'_>: Clone, Debug, Send, Sync);
-    assert_impl_all!(RefreshTokenExchangeRequestBody<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(
+        RefreshTokenExchangeRequestBody<'static>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Display,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
     assert_impl_all!(RefreshTokenExchangeRequest<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
     assert_impl_all!(
         RefreshTokenExchangeResponse: Clone,
@@ -166,6 +492,36 @@ mod tests {
         Serialize,
         Sync
     );
+    assert_fields!(
+        RefreshTokenExchangeResponseRef<'_>: access_token,
+        expires_in,
+        refresh_token,
+        scope,
+        token_type
+    );
+    assert_impl_all!(
+        RefreshTokenExchangeResponseRef<'_>: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_response_ref() {
+        let response: RefreshTokenExchangeResponseRef<'_> =
+            serde_json::from_slice(fixtures::REFRESH_TOKEN_RESPONSE).unwrap();
+        assert!(matches!(response.access_token, Cow::Borrowed(_)));
+        assert!(matches!(response.refresh_token, Cow::Borrowed(_)));
+
+        let debug = format!("{response:?}");
+        assert!(!debug.contains("Pb2fJ1XM8lA5Ow3fU0JMT2Wr6BLgPl"));
+        assert!(!debug.contains("x7eP5sUjcX9hRRSKW4mMZ6pJxvr1u9"));
+        assert_eq!(fixtures::refresh_token_response(), response.into_owned());
+    }
 
     #[test]
     fn test_refresh_token_exchange_request() {
@@ -173,35 +529,158 @@ mod tests {
         let refresh_token = "b";
         let mut builder = client.refresh_token_exchange(refresh_token);
         let req = builder.build();
+        assert_eq!(Method::Post, req.method());
         assert_eq!(
             req.headers,
-            &[("Content-Type", "application/x-www-form-urlencoded")]
+            [
+                (
+                    "Content-Type".into(),
+                    "application/x-www-form-urlencoded".into()
+                ),
+                ("User-Agent".into(), USER_AGENT.into()),
+            ]
+            .as_ref()
         );
         assert_eq!(req.url_base, "https://discord.com/api/v6/oauth2/token");
         assert_eq!(
             req.body,
             RefreshTokenExchangeRequestBody {
                 client_id: ApplicationId(1),
-                client_secret: "a",
+                client_secret: Cow::Borrowed("a"),
                 grant_type: GrantType::RefreshToken,
-                redirect_uri: "https://example.com/",
-                refresh_token,
-                scope: String::new(),
+                redirect_uri: Cow::Borrowed("https://example.com/"),
+                refresh_token: Cow::Borrowed(refresh_token),
+                scope: None,
             }
         );
+        assert_eq!(
+            "https://discord.com/api/v6/oauth2/token?\
+            client_id=1\
+            &client_secret=a\
+            &grant_type=refresh_token&redirect_uri=https%3A%2F%2Fexample.com%2F\
+            &refresh_token=b",
+            req.url(),
+        );
 
-        builder.scopes(&[Scope::Guilds, Scope::GdmJoin]);
+        builder.scopes([Scope::Guilds, Scope::GdmJoin]);
         let req = builder.build();
         assert_eq!(
             req.body,
             RefreshTokenExchangeRequestBody {
                 client_id: ApplicationId(1),
-                client_secret: "a",
+                client_secret: Cow::Borrowed("a"),
                 grant_type: GrantType::RefreshToken,
-                redirect_uri: "https://example.com/",
-                refresh_token,
-                scope: "guilds gdm.join".to_owned(),
+                redirect_uri: Cow::Borrowed("https://example.com/"),
+                refresh_token: Cow::Borrowed(refresh_token),
+                scope: Some(Cow::Owned("guilds gdm.join".to_owned())),
             }
         );
+        assert_eq!(
+            "https://discord.com/api/v6/oauth2/token?\
+            client_id=1\
+            &client_secret=a\
+            &grant_type=refresh_token&redirect_uri=https%3A%2F%2Fexample.com%2F\
+            &refresh_token=b\
+            &scope=guilds%20gdm.join",
+            req.url(),
+        );
+    }
+
+    #[test]
+    fn test_refresh_token_exchange_scope_body() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client.refresh_token_exchange("b");
+
+        let body = serde_json::to_value(builder.build().body).unwrap();
+        assert!(body.get("scope").is_none());
+
+        builder.scopes([Scope::Identify]);
+        let req = builder.build();
+        assert!(req.url().ends_with("&scope=identify"));
+        let body = serde_json::to_value(req.body).unwrap();
+        assert_eq!(Some("identify"), body["scope"].as_str());
+    }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+
+        let mut builder = client.refresh_token_exchange("token");
+        builder.scopes([Scope::Identify]);
+        let fluent = client
+            .refresh_token_exchange("token")
+            .with_scopes([Scope::Identify]);
+
+        assert_eq!(builder.build(), fluent.build());
+    }
+
+    #[test]
+    fn test_body_display() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client.refresh_token_exchange("token1234").build();
+        let display = request.body.to_string();
+
+        assert_eq!(
+            "client_id=1&client_secret=<redacted>&grant_type=refresh_token\
+            &redirect_uri=https://example.com/&refresh_token=<redacted>",
+            display
+        );
+        assert!(!display.contains("hunter2"));
+        assert!(!display.contains("token1234"));
+
+        let request = client
+            .refresh_token_exchange("token1234")
+            .with_scopes([Scope::Identify])
+            .build();
+        assert!(request.body.to_string().ends_with("&scope=identify"));
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client.refresh_token_exchange("token1234").build();
+        let curl = request.to_curl(false);
+
+        assert_eq!(
+            format!(
+                "curl -X POST 'https://discord.com/api/v6/oauth2/token' \\\n  \
+                -H 'Content-Type: application/x-www-form-urlencoded' \\\n  \
+                -H 'User-Agent: {USER_AGENT}' \\\n  \
+                --data-urlencode 'client_id=1' \\\n  \
+                --data-urlencode \"client_secret=$CLIENT_SECRET\" \\\n  \
+                --data-urlencode 'grant_type=refresh_token' \\\n  \
+                --data-urlencode 'redirect_uri=https://example.com/' \\\n  \
+                --data-urlencode \"refresh_token=$REFRESH_TOKEN\""
+            ),
+            curl
+        );
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains("token1234"));
+    }
+
+    #[test]
+    fn test_body_round_trip() {
+        let client = Client::new(ApplicationId(1), "hunter 2&", &["https://example.com"]).unwrap();
+        let request = client
+            .refresh_token_exchange("refresh1234")
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+
+        let json = serde_json::to_string(&request.body).unwrap();
+        let from_json = serde_json::from_str::<RefreshTokenExchangeRequestBody<'_>>(&json).unwrap();
+        assert_eq!(request.body, from_json);
+    }
+
+    #[test]
+    fn test_body_form_round_trip() {
+        let client = Client::new(ApplicationId(1), "hunter 2&", &["https://example.com"]).unwrap();
+        let request = client
+            .refresh_token_exchange("refresh+1234")
+            .with_scopes([Scope::Identify, Scope::Email])
+            .build();
+
+        let json = crate::request::tests::form_to_json(&request.body().unwrap());
+        let from_form = serde_json::from_str::<RefreshTokenExchangeRequestBody<'_>>(&json).unwrap();
+        assert_eq!(request.body, from_form);
     }
 }
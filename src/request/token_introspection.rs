@@ -0,0 +1,400 @@
+//! Create requests to introspect an access token or refresh token, and parse
+//! their responses.
+//!
+//! Introspection tells whether a token is active and what it grants. Discord
+//! doesn't publicly document an introspection endpoint, so the endpoint of
+//! the authorization server that issued the token must be passed to
+//! [`Client::introspect_token`].
+//!
+//! Refer to [RFC 7662] for additional information.
+//!
+//! [`Client::introspect_token`]: ../../client/struct.Client.html#method.introspect_token
+//! [RFC 7662]: https://tools.ietf.org/html/rfc7662
+
+use super::super::{
+    form::{self, FormWriter},
+    Client,
+};
+use super::{token_revocation::TokenTypeHint, CurlValue, Header, Headers, Method, OAuthRequest};
+use crate::id::ApplicationId;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct IntrospectionRequestBody<'a> {
+    /// ID of the application introspecting the token.
+    pub client_id: ApplicationId,
+    /// Secret of the application introspecting the token.
+    pub client_secret: &'a str,
+    /// Access token or refresh token to introspect.
+    pub token: &'a str,
+    /// Hint about the type of the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type_hint: Option<TokenTypeHint>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct IntrospectionRequest<'a> {
+    /// Body to send.
+    pub body: IntrospectionRequestBody<'a>,
+    /// Headers to send.
+    ///
+    /// This includes the [`USER_AGENT`].
+    ///
+    /// [`USER_AGENT`]: ../constant.USER_AGENT.html
+    pub headers: Headers,
+    /// Endpoint to send the request to.
+    pub url_base: &'a str,
+}
+
+impl IntrospectionRequest<'_> {
+    /// Return the HTTP method to send the request with.
+    ///
+    /// This is always [`Method::Post`].
+    ///
+    /// [`Method::Post`]: ../enum.Method.html#variant.Post
+    pub const fn method(&self) -> Method {
+        Method::Post
+    }
+
+    /// Render the request as a curl command, for debugging.
+    ///
+    /// Each body field is passed via `--data-urlencode`, with arguments
+    /// quoted for POSIX shells. Unless `unredacted` is set, the client secret
+    /// and token are replaced by the shell variables `$CLIENT_SECRET` and
+    /// `$TOKEN`, so the command can be shared without leaking them.
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        let client_id = self.body.client_id.0.to_string();
+        let mut fields = vec![
+            ("client_id", CurlValue::Plain(&client_id)),
+            (
+                "client_secret",
+                CurlValue::Secret(self.body.client_secret, "CLIENT_SECRET"),
+            ),
+            ("token", CurlValue::Secret(self.body.token, "TOKEN")),
+        ];
+
+        if let Some(hint) = self.body.token_type_hint {
+            fields.push(("token_type_hint", CurlValue::Plain(hint.name())));
+        }
+
+        super::curl(
+            self.method(),
+            self.url_base,
+            &self.headers,
+            None,
+            &fields,
+            unredacted,
+        )
+    }
+
+    /// Return the urlencoded body as bytes.
+    ///
+    /// This is the same as the [`body`] returned by [`OAuthRequest`].
+    ///
+    /// [`OAuthRequest`]: ../trait.OAuthRequest.html
+    /// [`body`]: ../trait.OAuthRequest.html#tymethod.body
+    pub fn body_bytes(&self) -> Vec<u8> {
+        self.form().into_bytes()
+    }
+
+    /// Urlencode the body.
+    fn form(&self) -> String {
+        form::build(|form| self.write_form(form))
+    }
+
+    fn write_form(&self, form: &mut dyn FormWriter) {
+        form.push_raw("client_id=");
+        form.push_number(self.body.client_id.0);
+        form.push_raw("&client_secret=");
+        form.push_encoded(self.body.client_secret);
+        form.push_raw("&token=");
+        form.push_encoded(self.body.token);
+
+        if let Some(hint) = self.body.token_type_hint {
+            form.push_raw("&token_type_hint=");
+            form.push_raw(hint.name());
+        }
+    }
+}
+
+impl OAuthRequest for IntrospectionRequest<'_> {
+    fn method(&self) -> Method {
+        IntrospectionRequest::method(self)
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.url_base)
+    }
+
+    fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.form())
+    }
+}
+
+/// Response from introspecting a token.
+///
+/// Only [`active`] is always present. Inactive tokens usually have no other
+/// fields, since the server doesn't disclose anything about them.
+///
+/// [`active`]: #structfield.active
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct IntrospectionResponse {
+    /// Whether the token is currently active.
+    pub active: bool,
+    /// ID of the client the token was issued to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Unix timestamp in seconds of when the token expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    /// Space-delimited list of scopes that the token grants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Identifier of the user who authorized the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// Human-readable name of the user who authorized the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+#[cfg(feature = "json")]
+impl IntrospectionResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by the server.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+/// Create a token introspection request.
+///
+/// # Examples
+///
+/// Introspect an access token:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{
+///     id::ApplicationId,
+///     request::{token_revocation::TokenTypeHint, OAuthRequest},
+///     Client,
+/// };
+///
+/// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+/// let request = client
+///     .introspect_token("https://auth.example.com/introspect", "access token")
+///     .with_token_type_hint(TokenTypeHint::AccessToken)
+///     .build();
+///
+/// assert_eq!("https://auth.example.com/introspect", request.endpoint());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntrospectionBuilder<'a> {
+    client: &'a Client,
+    endpoint: &'a str,
+    token: &'a str,
+    token_type_hint: Option<TokenTypeHint>,
+}
+
+impl<'a> IntrospectionBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: &'a str, token: &'a str) -> Self {
+        Self {
+            client,
+            endpoint,
+            token,
+            token_type_hint: None,
+        }
+    }
+
+    pub fn build(&self) -> IntrospectionRequest<'a> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "oauth2.introspect",
+            application_id = self.client.client_id().0,
+            token_type_hint = self.token_type_hint.map(TokenTypeHint::name),
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        tracing::debug!("built token introspection request");
+
+        IntrospectionRequest {
+            body: IntrospectionRequestBody {
+                client_id: self.client.client_id(),
+                client_secret: self.client.client_secret(),
+                token: self.token,
+                token_type_hint: self.token_type_hint,
+            },
+            headers: self.client.headers(),
+            url_base: self.endpoint,
+        }
+    }
+
+    /// Set a hint about the type of the token being introspected.
+    ///
+    /// When not set, the `token_type_hint` parameter is omitted and the
+    /// server searches for the token among all types.
+    pub fn token_type_hint(&mut self, hint: TokenTypeHint) -> &mut Self {
+        self.token_type_hint.replace(hint);
+
+        self
+    }
+
+    /// Set a hint about the type of the token being introspected.
+    ///
+    /// This is the by-value form of [`token_type_hint`], for building in one
+    /// expression.
+    ///
+    /// [`token_type_hint`]: #method.token_type_hint
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_token_type_hint(mut self, hint: TokenTypeHint) -> Self {
+        self.token_type_hint(hint);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        IntrospectionBuilder, IntrospectionRequest, IntrospectionRequestBody, IntrospectionResponse,
+    };
+    use crate::id::ApplicationId;
+    use crate::{
+        request::{token_revocation::TokenTypeHint, Method, OAuthRequest},
+        Client,
+    };
+    use serde::{Deserialize, Serialize};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::fmt::Debug;
+
+    assert_fields!(IntrospectionRequestBody<'_>: client_id, client_secret, token, token_type_hint);
+    assert_fields!(IntrospectionRequest<'_>: body, headers, url_base);
+    assert_fields!(IntrospectionResponse: active, client_id, exp, scope, sub, username);
+    assert_impl_all!(IntrospectionBuilder<'_>: Clone, Debug, Send, Sync);
+    assert_impl_all!(IntrospectionRequestBody<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(IntrospectionRequest<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(
+        IntrospectionResponse: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_introspection_request() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client.introspect_token("https://example.com/introspect", "b+c");
+
+        let request = builder.build();
+        assert_eq!(Method::Post, request.method());
+        assert_eq!("https://example.com/introspect", request.endpoint());
+        assert_eq!(
+            Some("client_id=1&client_secret=a&token=b%2Bc".to_owned()),
+            request.body()
+        );
+        assert_eq!(
+            b"client_id=1&client_secret=a&token=b%2Bc".to_vec(),
+            request.body_bytes()
+        );
+
+        builder.token_type_hint(TokenTypeHint::RefreshToken);
+        let request = builder.build();
+        assert_eq!(
+            Some(
+                "client_id=1&client_secret=a&token=b%2Bc&token_type_hint=refresh_token".to_owned()
+            ),
+            request.body()
+        );
+        assert_eq!("https://example.com/introspect", request.endpoint());
+        assert_eq!(client.headers().as_ref(), request.headers());
+    }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+
+        let mut builder = client.introspect_token("https://example.com", "token");
+        builder.token_type_hint(TokenTypeHint::AccessToken);
+        let fluent = client
+            .introspect_token("https://example.com", "token")
+            .with_token_type_hint(TokenTypeHint::AccessToken);
+
+        assert_eq!(builder.build(), fluent.build());
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client
+            .introspect_token("https://example.com/introspect", "token1234")
+            .build();
+        let curl = request.to_curl(false);
+
+        assert!(curl.starts_with("curl -X POST 'https://example.com/introspect'"));
+        assert!(curl.ends_with("--data-urlencode \"token=$TOKEN\""));
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains("token1234"));
+    }
+
+    #[test]
+    fn test_response() {
+        let active = serde_json::from_str::<IntrospectionResponse>(
+            r#"{
+                "active": true,
+                "client_id": "1",
+                "username": "jdoe",
+                "scope": "identify email",
+                "sub": "53908232506183680",
+                "exp": 1419356238,
+                "iat": 1419350238
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            IntrospectionResponse {
+                active: true,
+                client_id: Some("1".to_owned()),
+                exp: Some(1_419_356_238),
+                scope: Some("identify email".to_owned()),
+                sub: Some("53908232506183680".to_owned()),
+                username: Some("jdoe".to_owned()),
+            },
+            active
+        );
+
+        let inactive =
+            serde_json::from_str::<IntrospectionResponse>(r#"{"active":false}"#).unwrap();
+        assert!(!inactive.active);
+        assert!(inactive.scope.is_none());
+        assert_eq!(
+            r#"{"active":false}"#,
+            serde_json::to_string(&inactive).unwrap()
+        );
+    }
+}
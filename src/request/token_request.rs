@@ -0,0 +1,224 @@
+//! Any request to the token endpoint, regardless of grant.
+//!
+//! This is useful for handling requests of different grants together, such
+//! as queueing them in one list or logging them in one place.
+
+use super::{
+    access_token_exchange::AccessTokenExchangeRequest,
+    client_credentials_grant::ClientCredentialsGrantRequest,
+    refresh_token_exchange::RefreshTokenExchangeRequest, Header, Method, OAuthRequest,
+};
+use crate::GrantType;
+use std::borrow::Cow;
+
+/// Request to the token endpoint for any grant.
+///
+/// Every method delegates to the wrapped request, so the URL, headers, and
+/// body are the same as those of the request it was created from.
+///
+/// # Examples
+///
+/// Queue requests of different grants together:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{
+///     id::ApplicationId, request::token_request::TokenRequest, Client, GrantType,
+/// };
+///
+/// let client = Client::new(ApplicationId(1), "secret", &["https://example.com"])?;
+///
+/// let requests: Vec<TokenRequest<'_>> = vec![
+///     client.client_credentials_grant().build().into(),
+///     client.refresh_token_exchange("refresh token").build().into(),
+/// ];
+///
+/// assert_eq!(&GrantType::ClientCredentials, requests[0].grant_type());
+/// assert_eq!(&GrantType::RefreshToken, requests[1].grant_type());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TokenRequest<'a> {
+    /// Exchange of an authorization code.
+    AuthorizationCode(AccessTokenExchangeRequest<'a>),
+    /// Client credentials grant.
+    ClientCredentials(ClientCredentialsGrantRequest<'a>),
+    /// Exchange of a refresh token.
+    RefreshToken(RefreshTokenExchangeRequest<'a>),
+}
+
+impl TokenRequest<'_> {
+    /// Return the type of grant of the request.
+    pub const fn grant_type(&self) -> &GrantType {
+        match self {
+            Self::AuthorizationCode(request) => &request.body.grant_type,
+            Self::ClientCredentials(request) => &request.body.grant_type,
+            Self::RefreshToken(request) => &request.body.grant_type,
+        }
+    }
+
+    /// Return the HTTP method of the request.
+    pub const fn method(&self) -> Method {
+        match self {
+            Self::AuthorizationCode(request) => request.method(),
+            Self::ClientCredentials(request) => request.method(),
+            Self::RefreshToken(request) => request.method(),
+        }
+    }
+
+    /// Return the URL of the request, including its parameters.
+    pub fn url(&self) -> String {
+        match self {
+            Self::AuthorizationCode(request) => request.url(),
+            Self::ClientCredentials(request) => request.url(),
+            Self::RefreshToken(request) => request.url(),
+        }
+    }
+
+    /// Append the URL returned by [`url`] to a buffer.
+    ///
+    /// [`url`]: #method.url
+    pub fn write_url(&self, buf: &mut String) {
+        match self {
+            Self::AuthorizationCode(request) => request.write_url(buf),
+            Self::ClientCredentials(request) => request.write_url(buf),
+            Self::RefreshToken(request) => request.write_url(buf),
+        }
+    }
+
+    /// Render the request as a curl command.
+    ///
+    /// Refer to the `to_curl` method of the wrapped request for details.
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        match self {
+            Self::AuthorizationCode(request) => request.to_curl(unredacted),
+            Self::ClientCredentials(request) => request.to_curl(unredacted),
+            Self::RefreshToken(request) => request.to_curl(unredacted),
+        }
+    }
+}
+
+impl OAuthRequest for TokenRequest<'_> {
+    fn method(&self) -> Method {
+        self.method()
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        match self {
+            Self::AuthorizationCode(request) => request.endpoint(),
+            Self::ClientCredentials(request) => request.endpoint(),
+            Self::RefreshToken(request) => request.endpoint(),
+        }
+    }
+
+    fn headers(&self) -> &[Header] {
+        match self {
+            Self::AuthorizationCode(request) => request.headers(),
+            Self::ClientCredentials(request) => request.headers(),
+            Self::RefreshToken(request) => request.headers(),
+        }
+    }
+
+    fn body(&self) -> Option<String> {
+        match self {
+            Self::AuthorizationCode(request) => request.body(),
+            Self::ClientCredentials(request) => request.body(),
+            Self::RefreshToken(request) => request.body(),
+        }
+    }
+}
+
+impl<'a> From<AccessTokenExchangeRequest<'a>> for TokenRequest<'a> {
+    fn from(request: AccessTokenExchangeRequest<'a>) -> Self {
+        Self::AuthorizationCode(request)
+    }
+}
+
+impl<'a> From<ClientCredentialsGrantRequest<'a>> for TokenRequest<'a> {
+    fn from(request: ClientCredentialsGrantRequest<'a>) -> Self {
+        Self::ClientCredentials(request)
+    }
+}
+
+impl<'a> From<RefreshTokenExchangeRequest<'a>> for TokenRequest<'a> {
+    fn from(request: RefreshTokenExchangeRequest<'a>) -> Self {
+        Self::RefreshToken(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenRequest;
+    use crate::{
+        id::ApplicationId,
+        request::{
+            access_token_exchange::AccessTokenExchangeRequest,
+            client_credentials_grant::ClientCredentialsGrantRequest,
+            refresh_token_exchange::RefreshTokenExchangeRequest, OAuthRequest,
+        },
+        Client, GrantType, Scope,
+    };
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+
+    assert_impl_all!(
+        TokenRequest<'_>: Clone,
+        Debug,
+        Eq,
+        From<AccessTokenExchangeRequest<'static>>,
+        From<ClientCredentialsGrantRequest<'static>>,
+        From<RefreshTokenExchangeRequest<'static>>,
+        OAuthRequest,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    fn assert_delegates(direct: &impl OAuthRequest, request: &TokenRequest<'_>) {
+        assert_eq!(direct.method(), OAuthRequest::method(request));
+        assert_eq!(direct.endpoint(), request.endpoint());
+        assert_eq!(direct.headers(), request.headers());
+        assert_eq!(direct.body(), request.body());
+    }
+
+    #[test]
+    fn test_delegates() {
+        let client = Client::new(ApplicationId(1), "a b", &["https://example.com"]).unwrap();
+
+        let direct = client
+            .access_token_exchange("code", "https://example.com")
+            .unwrap()
+            .with_scopes([Scope::Identify])
+            .build();
+        let request = TokenRequest::from(direct.clone());
+        assert_delegates(&direct, &request);
+        assert_eq!(&GrantType::AuthorizationCode, request.grant_type());
+        assert_eq!(direct.url(), request.url());
+        assert_eq!(direct.to_curl(true), request.to_curl(true));
+
+        let direct = client
+            .client_credentials_grant()
+            .with_scopes([Scope::Identify])
+            .build();
+        let request = TokenRequest::from(direct.clone());
+        assert_delegates(&direct, &request);
+        assert_eq!(&GrantType::ClientCredentials, request.grant_type());
+        assert_eq!(direct.url(), request.url());
+        assert_eq!(direct.to_curl(true), request.to_curl(true));
+
+        let direct = client
+            .refresh_token_exchange("refresh")
+            .with_scopes([Scope::Identify])
+            .build();
+        let request = TokenRequest::from(direct.clone());
+        assert_delegates(&direct, &request);
+        assert_eq!(&GrantType::RefreshToken, request.grant_type());
+        assert_eq!(direct.url(), request.url());
+        assert_eq!(direct.to_curl(true), request.to_curl(true));
+
+        let mut buf = String::from("url: ");
+        request.write_url(&mut buf);
+        assert_eq!(format!("url: {}", direct.url()), buf);
+    }
+}
@@ -0,0 +1,350 @@
+//! Create requests to revoke an access token or refresh token.
+//!
+//! Refer to [RFC 7009] for additional information.
+//!
+//! [RFC 7009]: https://tools.ietf.org/html/rfc7009
+
+use super::super::{
+    form::{self, FormWriter},
+    Client,
+};
+use super::{CurlValue, Header, Headers, Method, OAuthRequest};
+use crate::id::ApplicationId;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Hint about the type of token being revoked.
+///
+/// This helps the server find the token faster. Refer to
+/// [RFC 7009 § 2.1] for more information.
+///
+/// [RFC 7009 § 2.1]: https://tools.ietf.org/html/rfc7009#section-2.1
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(rename_all = "snake_case")]
+pub enum TokenTypeHint {
+    /// The token is an access token.
+    AccessToken,
+    /// The token is a refresh token.
+    RefreshToken,
+}
+
+impl TokenTypeHint {
+    /// Return the name of the hint.
+    ///
+    /// This is equivalent to what you would get when serializing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::request::token_revocation::TokenTypeHint;
+    ///
+    /// assert_eq!("access_token", TokenTypeHint::AccessToken.name());
+    /// assert_eq!("refresh_token", TokenTypeHint::RefreshToken.name());
+    /// ```
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AccessToken => "access_token",
+            Self::RefreshToken => "refresh_token",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TokenRevocationRequestBody<'a> {
+    /// ID of the application that was authorized.
+    pub client_id: ApplicationId,
+    /// Secret of the application that was authorized.
+    pub client_secret: &'a str,
+    /// Access token or refresh token to revoke.
+    pub token: &'a str,
+    /// Hint about the type of the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type_hint: Option<TokenTypeHint>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TokenRevocationRequest<'a> {
+    /// Body to send.
+    pub body: TokenRevocationRequestBody<'a>,
+    /// Headers to send.
+    ///
+    /// This includes the [`USER_AGENT`].
+    ///
+    /// [`USER_AGENT`]: ../constant.USER_AGENT.html
+    pub headers: Headers,
+    /// Base of the URL.
+    pub url_base: &'static str,
+}
+
+impl TokenRevocationRequest<'_> {
+    /// Return the HTTP method to send the request with.
+    ///
+    /// This is always [`Method::Post`].
+    ///
+    /// [`Method::Post`]: ../enum.Method.html#variant.Post
+    pub const fn method(&self) -> Method {
+        Method::Post
+    }
+
+    /// Render the request as a curl command, for debugging.
+    ///
+    /// Each body field is passed via `--data-urlencode`, with arguments
+    /// quoted for POSIX shells. Unless `unredacted` is set, the client secret
+    /// and token are replaced by the shell variables `$CLIENT_SECRET` and
+    /// `$TOKEN`, so the command can be shared without leaking them.
+    pub fn to_curl(&self, unredacted: bool) -> String {
+        let client_id = self.body.client_id.0.to_string();
+        let mut fields = vec![
+            ("client_id", CurlValue::Plain(&client_id)),
+            (
+                "client_secret",
+                CurlValue::Secret(self.body.client_secret, "CLIENT_SECRET"),
+            ),
+            ("token", CurlValue::Secret(self.body.token, "TOKEN")),
+        ];
+
+        if let Some(hint) = self.body.token_type_hint {
+            fields.push(("token_type_hint", CurlValue::Plain(hint.name())));
+        }
+
+        super::curl(
+            self.method(),
+            self.url_base,
+            &self.headers,
+            None,
+            &fields,
+            unredacted,
+        )
+    }
+
+    /// Urlencode the body.
+    fn form(&self) -> String {
+        form::build(|form| self.write_form(form))
+    }
+
+    fn write_form(&self, form: &mut dyn FormWriter) {
+        form.push_raw("client_id=");
+        form.push_number(self.body.client_id.0);
+        form.push_raw("&client_secret=");
+        form.push_encoded(self.body.client_secret);
+        form.push_raw("&token=");
+        form.push_encoded(self.body.token);
+
+        if let Some(hint) = self.body.token_type_hint {
+            form.push_raw("&token_type_hint=");
+            form.push_raw(hint.name());
+        }
+    }
+}
+
+impl OAuthRequest for TokenRevocationRequest<'_> {
+    fn method(&self) -> Method {
+        TokenRevocationRequest::method(self)
+    }
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.url_base)
+    }
+
+    fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.form())
+    }
+}
+
+/// Create a token revocation request.
+///
+/// # Examples
+///
+/// Revoke a refresh token:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{
+///     id::ApplicationId,
+///     request::{token_revocation::TokenTypeHint, OAuthRequest},
+///     Client,
+/// };
+///
+/// let client = Client::new(ApplicationId(123), "secret", &["https://example.com"])?;
+/// let request = client
+///     .token_revocation("refresh token")
+///     .with_token_type_hint(TokenTypeHint::RefreshToken)
+///     .build();
+///
+/// println!("revocation body: {:?}", request.body());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TokenRevocationBuilder<'a> {
+    client: &'a Client,
+    token: &'a str,
+    token_type_hint: Option<TokenTypeHint>,
+}
+
+impl<'a> TokenRevocationBuilder<'a> {
+    const BASE_URL: &'static str = "https://discord.com/api/v6/oauth2/token/revoke";
+
+    pub(crate) fn new(client: &'a Client, token: &'a str) -> Self {
+        Self {
+            client,
+            token,
+            token_type_hint: None,
+        }
+    }
+
+    pub fn build(&self) -> TokenRevocationRequest<'a> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "oauth2.revoke",
+            application_id = self.client.client_id().0,
+            token_type_hint = self.token_type_hint.map(TokenTypeHint::name),
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        tracing::debug!("built token revocation request");
+
+        TokenRevocationRequest {
+            body: TokenRevocationRequestBody {
+                client_id: self.client.client_id(),
+                client_secret: self.client.client_secret(),
+                token: self.token,
+                token_type_hint: self.token_type_hint,
+            },
+            headers: self.client.headers(),
+            url_base: Self::BASE_URL,
+        }
+    }
+
+    /// Set a hint about the type of the token being revoked.
+    ///
+    /// When not set, the `token_type_hint` parameter is omitted and the
+    /// server searches for the token among all types.
+    pub fn token_type_hint(&mut self, hint: TokenTypeHint) -> &mut Self {
+        self.token_type_hint.replace(hint);
+
+        self
+    }
+
+    /// Set a hint about the type of the token being revoked.
+    ///
+    /// This is the by-value form of [`token_type_hint`], for building in one
+    /// expression.
+    ///
+    /// [`token_type_hint`]: #method.token_type_hint
+    #[must_use = "the builder is returned with the value set"]
+    pub fn with_token_type_hint(mut self, hint: TokenTypeHint) -> Self {
+        self.token_type_hint(hint);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        TokenRevocationBuilder, TokenRevocationRequest, TokenRevocationRequestBody, TokenTypeHint,
+    };
+    use crate::id::ApplicationId;
+    use crate::{
+        request::{Method, OAuthRequest},
+        Client,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_test::Token;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_fields!(TokenRevocationRequestBody<'_>: client_id, client_secret, token, token_type_hint);
+    assert_fields!(TokenRevocationRequest<'_>: body, headers, url_base);
+    assert_impl_all!(TokenRevocationBuilder<'_>: Clone, Debug, Send, Sync);
+    assert_impl_all!(TokenRevocationRequestBody<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(TokenRevocationRequest<'_>: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+    assert_impl_all!(
+        TokenTypeHint: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_token_type_hint() {
+        serde_test::assert_tokens(
+            &TokenTypeHint::AccessToken,
+            &[Token::UnitVariant {
+                name: "TokenTypeHint",
+                variant: "access_token",
+            }],
+        );
+        serde_test::assert_tokens(
+            &TokenTypeHint::RefreshToken,
+            &[Token::UnitVariant {
+                name: "TokenTypeHint",
+                variant: "refresh_token",
+            }],
+        );
+    }
+
+    #[test]
+    fn test_token_revocation_request() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let mut builder = client.token_revocation("b+c");
+
+        let request = builder.build();
+        assert_eq!(Method::Post, request.method());
+        assert_eq!(
+            "https://discord.com/api/v6/oauth2/token/revoke",
+            request.endpoint()
+        );
+        assert_eq!(
+            Some("client_id=1&client_secret=a&token=b%2Bc".to_owned()),
+            request.body()
+        );
+
+        builder.token_type_hint(TokenTypeHint::AccessToken);
+        assert_eq!(
+            Some("client_id=1&client_secret=a&token=b%2Bc&token_type_hint=access_token".to_owned()),
+            builder.build().body()
+        );
+    }
+
+    #[test]
+    fn test_fluent() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+
+        let mut builder = client.token_revocation("token");
+        builder.token_type_hint(TokenTypeHint::AccessToken);
+        let fluent = client
+            .token_revocation("token")
+            .with_token_type_hint(TokenTypeHint::AccessToken);
+
+        assert_eq!(builder.build(), fluent.build());
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let client = Client::new(ApplicationId(1), "hunter2", &["https://example.com"]).unwrap();
+        let request = client
+            .token_revocation("token1234")
+            .with_token_type_hint(TokenTypeHint::RefreshToken)
+            .build();
+        let curl = request.to_curl(false);
+
+        assert!(curl.contains("--data-urlencode \"token=$TOKEN\""));
+        assert!(curl.ends_with("--data-urlencode 'token_type_hint=refresh_token'"));
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains("token1234"));
+    }
+}
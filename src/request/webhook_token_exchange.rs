@@ -6,8 +6,12 @@
 //! [`WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
 //! [Discord's documentation]: https://discord.com/developers/docs/topics/oauth2#webhooks
 
-use super::{super::TokenType, access_token_exchange::AccessTokenExchangeRequest};
+use super::{
+    super::{AccessToken, RefreshToken, TokenResponse, TokenType},
+    access_token_exchange::AccessTokenExchangeRequest,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
 use twilight_model::channel::Webhook;
 
 pub type WebhookTokenExchangeRequest<'a> = AccessTokenExchangeRequest<'a>;
@@ -16,14 +20,17 @@ pub type WebhookTokenExchangeRequest<'a> = AccessTokenExchangeRequest<'a>;
 /// scope is specified. The request authorization URL can be built via
 /// [`AuthorizationUrlBuilder::webhook`].
 ///
+/// The tokens, including the token of the webhook, are redacted from the
+/// `Debug` output.
+///
 /// [`AuthorizationUrlBuilder::webhook`]: ../../authorization_url/struct.AuthorizationUrlBuilder.html#method.webhook
 /// [`WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct WebhookTokenExchangeResponse {
     /// Access token to be used when making requests to the API on the user's
     /// behalf.
-    pub access_token: String,
+    pub access_token: AccessToken,
     /// Number of seconds from issuing that the access token is valid.
     ///
     /// After this duration, the refresh token must be exchanged for another
@@ -31,7 +38,7 @@ pub struct WebhookTokenExchangeResponse {
     pub expires_in: u64,
     /// Refresh token to use to exchange for another access token and refresh
     /// token pair.
-    pub refresh_token: String,
+    pub refresh_token: RefreshToken,
     /// Space-delimited list of scopes that the token has had approved.
     pub scope: String,
     /// Type of token provided.
@@ -44,9 +51,83 @@ pub struct WebhookTokenExchangeResponse {
     pub webhook: Webhook,
 }
 
+#[cfg(feature = "json")]
+impl WebhookTokenExchangeResponse {
+    /// Parse a response from a JSON body.
+    ///
+    /// This is only available with the `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResponseParseError::OAuth`] if the body is an OAuth error
+    /// returned by Discord.
+    ///
+    /// Returns [`ResponseParseError::Deserializing`] if the body is neither
+    /// a response nor an OAuth error.
+    ///
+    /// [`ResponseParseError::Deserializing`]: ../../enum.ResponseParseError.html#variant.Deserializing
+    /// [`ResponseParseError::OAuth`]: ../../enum.ResponseParseError.html#variant.OAuth
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, crate::ResponseParseError> {
+        crate::json::from_json_bytes(bytes)
+    }
+}
+
+impl Debug for WebhookTokenExchangeResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("WebhookTokenExchangeResponse")
+            .field("access_token", &self.access_token)
+            .field("expires_in", &self.expires_in)
+            .field("refresh_token", &self.refresh_token)
+            .field("scope", &self.scope)
+            .field("token_type", &self.token_type)
+            .field("webhook", &RedactedWebhook(&self.webhook))
+            .finish()
+    }
+}
+
+impl TokenResponse for WebhookTokenExchangeResponse {
+    fn access_token(&self) -> &AccessToken {
+        &self.access_token
+    }
+
+    fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    fn refresh_token(&self) -> Option<&RefreshToken> {
+        Some(&self.refresh_token)
+    }
+
+    fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+}
+
+/// Debug formatting of a webhook with its token redacted.
+pub(crate) struct RedactedWebhook<'a>(pub(crate) &'a Webhook);
+
+impl Debug for RedactedWebhook<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.0.token.is_none() {
+            return Debug::fmt(self.0, f);
+        }
+
+        let mut webhook = self.0.clone();
+        webhook.token.replace("<redacted>".to_owned());
+
+        Debug::fmt(&webhook, f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::WebhookTokenExchangeResponse;
+    use super::{WebhookTokenExchangeRequest, WebhookTokenExchangeResponse};
+    use crate::id::ApplicationId;
+    use crate::{request::Method, Client};
     use serde::{Deserialize, Serialize};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::fmt::Debug;
@@ -69,4 +150,15 @@ mod tests {
         Serialize,
         Sync
     );
+
+    #[test]
+    fn test_method() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let builder = client
+            .access_token_exchange("b", "https://example.com")
+            .unwrap();
+        let request: WebhookTokenExchangeRequest<'_> = builder.build();
+
+        assert_eq!(Method::Post, request.method());
+    }
 }
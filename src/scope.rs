@@ -1,6 +1,70 @@
+//! OAuth 2 scopes and utilities for working with lists of them.
+
 use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// Parsing a scope from its name failed.
+///
+/// This is returned from the `FromStr` implementation of [`Scope`].
+///
+/// [`Scope`]: enum.Scope.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ScopeParseError {
+    /// The name isn't a scope known to this version of the crate.
+    Unknown {
+        /// Name that was parsed.
+        name: String,
+    },
+}
+
+impl Display for ScopeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Unknown { name } => f.write_fmt(format_args!("`{name}` is not a known scope")),
+        }
+    }
+}
+
+impl Error for ScopeParseError {}
 
 /// OAuth 2 scopes that must be requested for access to different resources.
+///
+/// # Matching on scopes
+///
+/// Discord adds new scopes over time, so this enum is marked as
+/// `#[non_exhaustive]`. This allows new variants to be added in a minor
+/// release without breaking downstream code, but it means that a `match` on a
+/// scope outside of this crate must include a wildcard arm.
+///
+/// The wildcard arm should handle scopes your application doesn't know about
+/// defensively, such as by refusing to grant access that depends on them,
+/// rather than panicking:
+///
+/// ```
+/// use twilight_oauth2::Scope;
+///
+/// fn describe(scope: Scope) -> &'static str {
+///     match scope {
+///         Scope::Bot => "add a bot to a guild",
+///         Scope::Email => "read your email address",
+///         Scope::Identify => "read your account information",
+///         // Scopes added in newer versions of the crate end up here.
+///         _ => "unknown access",
+///     }
+/// }
+///
+/// assert_eq!("add a bot to a guild", describe(Scope::Bot));
+/// assert_eq!("unknown access", describe(Scope::RpcApi));
+/// ```
+///
+/// Deserializing a scope name that isn't known to this version of the crate
+/// results in an error.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 #[serde(rename_all = "snake_case")]
@@ -45,6 +109,9 @@ pub enum Scope {
     /// Add users to guilds.
     #[serde(rename = "guilds.join")]
     GuildsJoin,
+    /// Read the user's member information in guilds they're in.
+    #[serde(rename = "guilds.members.read")]
+    GuildsMembersRead,
     /// Retrieve basic user information without an email address.
     Identify,
     /// Read messages from all client channels.
@@ -58,6 +125,9 @@ pub enum Scope {
     /// This is only available to allowlisted applications.
     #[serde(rename = "relationships.read")]
     RelationshipsRead,
+    /// Update the user's connection and metadata for the application.
+    #[serde(rename = "role_connections.write")]
+    RoleConnectionsWrite,
     /// Control a user's local Discord client via local RPC API access.
     ///
     /// This is only available to allowlisted applications.
@@ -72,6 +142,10 @@ pub enum Scope {
     /// This is only available to allowlisted applications.
     #[serde(rename = "rpc.notifications.read")]
     RpcNotificationsRead,
+    /// Connect to voice on the user's behalf and see all voice members.
+    ///
+    /// This is only available to allowlisted applications.
+    Voice,
     /// Generate a webhook for a selected guild and channel.
     ///
     /// The webhook is returned in the OAuth token response for authorization
@@ -80,10 +154,101 @@ pub enum Scope {
     WebhookIncoming,
 }
 
+/// Number of scopes known to this version of the crate.
+///
+/// This is the length of [`Scope::all`].
+///
+/// [`Scope::all`]: enum.Scope.html#method.all
+pub const SCOPE_COUNT: usize = Scope::all().len();
+
 impl Scope {
+    /// Minimum scopes required for inviting a bot to a guild.
+    ///
+    /// Use this with the [`BotAuthorizationUrlBuilder`], appending other
+    /// scopes as needed.
+    ///
+    /// [`BotAuthorizationUrlBuilder`]: ../authorization_url/struct.BotAuthorizationUrlBuilder.html
+    pub const BOT_INVITE_MINIMUM: &'static [Self] = &[Self::Bot];
+
+    /// Minimum scopes required for identifying a user including their email
+    /// address.
+    ///
+    /// Use this for a login flow that also needs to contact the user.
+    pub const EMAIL_REQUIRED: &'static [Self] = &[Self::Identify, Self::Email];
+
+    /// Minimum scopes required for identifying a user.
+    ///
+    /// Use this for a login flow that only needs the user's ID, username, and
+    /// avatar.
+    pub const IDENTIFY_REQUIRED: &'static [Self] = &[Self::Identify];
+
+    /// Return a list of every scope known to this version of the crate.
+    ///
+    /// Scopes are listed in alphabetical order of their [names].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::{scope, Scope};
+    ///
+    /// assert!(Scope::all().contains(&Scope::Identify));
+    /// assert_eq!(scope::SCOPE_COUNT, Scope::all().len());
+    /// ```
+    ///
+    /// [names]: #method.name
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::ActivitiesRead,
+            Self::ActivitiesWrite,
+            Self::ApplicationsBuildsRead,
+            Self::ApplicationsBuildsUpload,
+            Self::ApplicationsEntitlements,
+            Self::ApplicationsStoreUpdate,
+            Self::Bot,
+            Self::Connections,
+            Self::Email,
+            Self::GdmJoin,
+            Self::Guilds,
+            Self::GuildsJoin,
+            Self::GuildsMembersRead,
+            Self::Identify,
+            Self::MessagesRead,
+            Self::RelationshipsRead,
+            Self::RoleConnectionsWrite,
+            Self::Rpc,
+            Self::RpcApi,
+            Self::RpcNotificationsRead,
+            Self::Voice,
+            Self::WebhookIncoming,
+        ]
+    }
+
+    /// Parse a scope from its name, ignoring ASCII case.
+    ///
+    /// Canonical scope names are lowercase, but names in other casings such as
+    /// `"Identify"` or `"WebHook.Incoming"` are accepted too. Returns `None`
+    /// if the name isn't a scope known to this version of the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert_eq!(Some(Scope::Identify), Scope::from_discord_api_name("identify"));
+    /// assert_eq!(Some(Scope::GuildsJoin), Scope::from_discord_api_name("GUILDS.JOIN"));
+    /// assert!(Scope::from_discord_api_name("unknown").is_none());
+    /// ```
+    pub fn from_discord_api_name(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|scope| scope.name().eq_ignore_ascii_case(name))
+    }
+
     /// Return the name of the scope.
     ///
-    /// This is equivalent to what you would get when serializing it.
+    /// This is equivalent to what you would get when serializing it, and is
+    /// always lowercase.
     ///
     /// # Examples
     ///
@@ -93,7 +258,7 @@ impl Scope {
     /// assert_eq!("bot", Scope::Bot.name());
     /// assert_eq!("identify", Scope::Identify.name());
     /// ```
-    pub fn name(&self) -> &str {
+    pub fn name(&self) -> &'static str {
         match self {
             Self::ActivitiesRead => "activities.read",
             Self::ActivitiesWrite => "activities.write",
@@ -107,17 +272,215 @@ impl Scope {
             Self::GdmJoin => "gdm.join",
             Self::Guilds => "guilds",
             Self::GuildsJoin => "guilds.join",
+            Self::GuildsMembersRead => "guilds.members.read",
             Self::Identify => "identify",
             Self::MessagesRead => "messages.read",
             Self::RelationshipsRead => "relationships.read",
+            Self::RoleConnectionsWrite => "role_connections.write",
             Self::Rpc => "rpc",
             Self::RpcApi => "rpc.api",
             Self::RpcNotificationsRead => "rpc.notifications.read",
+            Self::Voice => "voice",
             Self::WebhookIncoming => "webhook.incoming",
         }
     }
+
+    /// Whether the scope can only be granted by a user approving an
+    /// authorization code grant.
+    ///
+    /// Tokens with these scopes can't be created via a client credentials
+    /// grant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert!(Scope::ActivitiesRead.requires_authorization_code_grant());
+    /// assert!(!Scope::Identify.requires_authorization_code_grant());
+    /// ```
+    pub fn requires_authorization_code_grant(&self) -> bool {
+        matches!(
+            self,
+            Self::ActivitiesRead | Self::ActivitiesWrite | Self::WebhookIncoming
+        )
+    }
+
+    /// Whether the scope grants access to personal data of the user.
+    ///
+    /// This is useful for highlighting these scopes when asking for consent
+    /// and for auditing which personal data an application can access, such
+    /// as for the GDPR or CCPA.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert!(Scope::Email.is_sensitive());
+    /// assert!(!Scope::Bot.is_sensitive());
+    /// ```
+    pub fn is_sensitive(&self) -> bool {
+        // Exhaustive so that new scopes have to be classified.
+        match self {
+            Self::ActivitiesRead
+            | Self::Connections
+            | Self::Email
+            | Self::Guilds
+            | Self::GuildsMembersRead
+            | Self::Identify
+            | Self::MessagesRead
+            | Self::RelationshipsRead
+            | Self::RpcNotificationsRead => true,
+            Self::ActivitiesWrite
+            | Self::ApplicationsBuildsRead
+            | Self::ApplicationsBuildsUpload
+            | Self::ApplicationsEntitlements
+            | Self::ApplicationsStoreUpdate
+            | Self::Bot
+            | Self::GdmJoin
+            | Self::GuildsJoin
+            | Self::RoleConnectionsWrite
+            | Self::Rpc
+            | Self::RpcApi
+            | Self::Voice
+            | Self::WebhookIncoming => false,
+        }
+    }
+
+    /// Return a short, human-readable description of what the scope allows.
+    ///
+    /// This is a sentence for showing to users before they authorize an
+    /// application, worded like Discord's own authorization screen where it
+    /// describes the scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert_eq!(
+    ///     "Access your username, avatar, and banner",
+    ///     Scope::Identify.description(),
+    /// );
+    /// ```
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ActivitiesRead => "Access your activity history",
+            Self::ActivitiesWrite => "Update your activity",
+            Self::ApplicationsBuildsRead => "Read build data of your applications",
+            Self::ApplicationsBuildsUpload => "Upload and update builds of your applications",
+            Self::ApplicationsEntitlements => "Read entitlements of your applications",
+            Self::ApplicationsStoreUpdate => "Read and update store data of your applications",
+            Self::Bot => "Add a bot to a server",
+            Self::Connections => "Access your third-party connections",
+            Self::Email => "Access your email address",
+            Self::GdmJoin => "Join group DMs on your behalf",
+            Self::Guilds => "Know what servers you're in",
+            Self::GuildsJoin => "Join servers for you",
+            Self::GuildsMembersRead => "Read your member info in servers",
+            Self::Identify => "Access your username, avatar, and banner",
+            Self::MessagesRead => "Read all messages in your client",
+            Self::RelationshipsRead => "Access your friends list",
+            Self::RoleConnectionsWrite => "Update your linked roles metadata",
+            Self::Rpc => "Control your Discord client",
+            Self::RpcApi => "Access the API as you through your Discord client",
+            Self::RpcNotificationsRead => "Receive your notifications through your Discord client",
+            Self::Voice => "Connect to voice channels on your behalf",
+            Self::WebhookIncoming => "Create a webhook in a channel",
+        }
+    }
+
+    /// Return a short, title-cased label of the scope.
+    ///
+    /// Unlike the [name], this is meant for showing to users, such as the
+    /// heading of a [description].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert_eq!("Join Servers", Scope::GuildsJoin.display_name());
+    /// ```
+    ///
+    /// [description]: #method.description
+    /// [name]: #method.name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::ActivitiesRead => "Read Activities",
+            Self::ActivitiesWrite => "Write Activities",
+            Self::ApplicationsBuildsRead => "Read Application Builds",
+            Self::ApplicationsBuildsUpload => "Upload Application Builds",
+            Self::ApplicationsEntitlements => "Application Entitlements",
+            Self::ApplicationsStoreUpdate => "Update Application Store",
+            Self::Bot => "Bot",
+            Self::Connections => "Connections",
+            Self::Email => "Email",
+            Self::GdmJoin => "Join Group DMs",
+            Self::Guilds => "Servers",
+            Self::GuildsJoin => "Join Servers",
+            Self::GuildsMembersRead => "Read Server Members",
+            Self::Identify => "Identify",
+            Self::MessagesRead => "Read Messages",
+            Self::RelationshipsRead => "Read Relationships",
+            Self::RoleConnectionsWrite => "Write Role Connections",
+            Self::Rpc => "RPC",
+            Self::RpcApi => "RPC API",
+            Self::RpcNotificationsRead => "Read RPC Notifications",
+            Self::Voice => "Voice",
+            Self::WebhookIncoming => "Incoming Webhook",
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.name())
+    }
 }
 
+impl FromStr for Scope {
+    type Err = ScopeParseError;
+
+    /// Parse a scope from its name, ignoring ASCII case.
+    ///
+    /// This is equivalent to [`Scope::from_discord_api_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::Scope;
+    ///
+    /// assert_eq!(Ok(Scope::ActivitiesRead), "activities.read".parse());
+    /// assert!("unknown".parse::<Scope>().is_err());
+    /// ```
+    ///
+    /// [`Scope::from_discord_api_name`]: #method.from_discord_api_name
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::from_discord_api_name(name).ok_or_else(|| ScopeParseError::Unknown {
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// Join a list of scopes into a space-delimited string.
+///
+/// This is the format Discord accepts and returns scopes in.
+///
+/// Scopes are joined in the order provided, and duplicates are kept. Use
+/// [`normalize`] first if you need alphabetical ordering and deduplication,
+/// such as when using the joined string as a cache key.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::{scope, Scope};
+///
+/// assert_eq!("bot identify", scope::join(&[Scope::Bot, Scope::Identify]));
+/// ```
+///
+/// [`normalize`]: fn.normalize.html
 pub fn join(scopes: &[Scope]) -> String {
     let mut buf = String::new();
 
@@ -134,30 +497,346 @@ pub fn join(scopes: &[Scope]) -> String {
     buf
 }
 
+/// Join a list of scopes like [`join`], borrowing the name when there is at
+/// most one scope.
+///
+/// [`join`]: fn.join.html
+pub(crate) fn join_cow(scopes: &[Scope]) -> Cow<'static, str> {
+    match scopes {
+        [] => Cow::Borrowed(""),
+        [scope] => Cow::Borrowed(scope.name()),
+        _ => Cow::Owned(join(scopes)),
+    }
+}
+
+/// Parse a space-delimited string of scopes, ignoring ASCII case.
+///
+/// This is the format Discord returns scopes in, such as in the `scope`
+/// field of token responses. Names are matched with
+/// [`Scope::from_discord_api_name`]. Names that aren't scopes known to this
+/// version of the crate are skipped, since Discord adds scopes over time.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::{scope, Scope};
+///
+/// assert_eq!(
+///     vec![Scope::Identify, Scope::Guilds],
+///     scope::parse("identify guilds"),
+/// );
+/// ```
+///
+/// [`Scope::from_discord_api_name`]: enum.Scope.html#method.from_discord_api_name
+pub fn parse(scopes: &str) -> Vec<Scope> {
+    scopes
+        .split_whitespace()
+        .filter_map(Scope::from_discord_api_name)
+        .collect()
+}
+
+/// Normalize a list of scopes into a canonical representation.
+///
+/// Duplicate scopes are removed and the remaining scopes are sorted
+/// alphabetically by their [name]. Joining the result with [`join`] gives the
+/// canonical string representation of the scopes.
+///
+/// Discord doesn't guarantee the order of the scopes it returns, so two scope
+/// lists or strings should both be normalized before comparing them for
+/// equality.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::{scope, Scope};
+///
+/// let scopes = scope::normalize(&[Scope::Identify, Scope::Guilds, Scope::Identify]);
+/// assert_eq!([Scope::Guilds, Scope::Identify], scopes.as_slice());
+/// assert_eq!("guilds identify", scope::join(&scopes));
+/// ```
+///
+/// [`join`]: fn.join.html
+/// [name]: enum.Scope.html#method.name
+pub fn normalize(scopes: &[Scope]) -> Vec<Scope> {
+    let mut normalized = scopes.to_vec();
+    normalized.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+    normalized.dedup();
+
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Scope;
+    use super::{Scope, ScopeParseError};
     use serde::{Deserialize, Serialize};
-    use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use serde_test::Token;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        str::FromStr,
+    };
 
+    assert_fields!(ScopeParseError::Unknown: name);
+    assert_impl_all!(ScopeParseError: Clone, Debug, Display, Eq, Error, PartialEq, Send, Sync);
     assert_impl_all!(
         Scope: Clone,
         Copy,
         Debug,
         Deserialize<'static>,
+        Display,
         Eq,
+        FromStr,
         PartialEq,
         Send,
         Serialize,
         Sync
     );
 
+    #[test]
+    fn test_descriptions() {
+        for scope in Scope::all() {
+            assert!(!scope.description().is_empty());
+            assert!(scope.description().len() <= 120);
+            assert!(!scope.display_name().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        /// Whether a scope is in `Scope::all`, usable in constants.
+        const fn listed(scope: Scope) -> bool {
+            let all = Scope::all();
+            let mut index = 0;
+
+            while index < all.len() {
+                if all[index] as u8 == scope as u8 {
+                    return true;
+                }
+
+                index += 1;
+            }
+
+            false
+        }
+
+        // Adding a scope makes the match non-exhaustive. Add the new scope
+        // here and to `Scope::all`, which every scope here is asserted to be
+        // in at compile time.
+        macro_rules! exhaustive {
+            ($($scope:ident,)*) => {
+                #[allow(dead_code)]
+                fn exhaustive(scope: Scope) {
+                    match scope {
+                        $(Scope::$scope => {})*
+                    }
+                }
+
+                $(const _: () = assert!(listed(Scope::$scope));)*
+
+                assert_eq!([$(Scope::$scope,)*].len(), super::SCOPE_COUNT);
+            };
+        }
+
+        exhaustive!(
+            ActivitiesRead,
+            ActivitiesWrite,
+            ApplicationsBuildsRead,
+            ApplicationsBuildsUpload,
+            ApplicationsEntitlements,
+            ApplicationsStoreUpdate,
+            Bot,
+            Connections,
+            Email,
+            GdmJoin,
+            Guilds,
+            GuildsJoin,
+            GuildsMembersRead,
+            Identify,
+            MessagesRead,
+            RelationshipsRead,
+            RoleConnectionsWrite,
+            Rpc,
+            RpcApi,
+            RpcNotificationsRead,
+            Voice,
+            WebhookIncoming,
+        );
+
+        assert_eq!(22, super::SCOPE_COUNT);
+        assert_eq!(super::normalize(Scope::all()), Scope::all());
+    }
+
     #[test]
     fn test_join() {
         assert!(super::join(&[]).is_empty());
         assert_eq!("bot", super::join(&[Scope::Bot]));
         assert_eq!("bot identify", super::join(&[Scope::Bot, Scope::Identify]));
+        assert_eq!(
+            "activities.read activities.write",
+            super::join(&[Scope::ActivitiesRead, Scope::ActivitiesWrite])
+        );
+    }
+
+    #[test]
+    fn test_join_cow() {
+        use std::borrow::Cow;
+
+        assert!(matches!(super::join_cow(&[]), Cow::Borrowed("")));
+        assert!(matches!(
+            super::join_cow(&[Scope::Identify]),
+            Cow::Borrowed("identify")
+        ));
+
+        let scopes = [Scope::Bot, Scope::Identify, Scope::Bot];
+        assert_eq!(super::join(&scopes), super::join_cow(&scopes));
+    }
+
+    #[test]
+    fn test_join_order() {
+        assert_eq!(
+            "guilds identify",
+            super::join(&[Scope::Guilds, Scope::Identify])
+        );
+        assert_eq!(
+            "identify guilds",
+            super::join(&[Scope::Identify, Scope::Guilds])
+        );
+        assert_eq!(
+            "identify guilds identify",
+            super::join(&[Scope::Identify, Scope::Guilds, Scope::Identify])
+        );
+    }
+
+    #[test]
+    fn test_from_discord_api_name() {
+        for scope in Scope::all() {
+            assert_eq!(Some(*scope), Scope::from_discord_api_name(scope.name()));
+            assert_eq!(
+                Some(*scope),
+                Scope::from_discord_api_name(&scope.name().to_uppercase())
+            );
+            assert!(scope.name().chars().all(|c| !c.is_ascii_uppercase()));
+        }
+
+        assert_eq!(
+            Some(Scope::WebhookIncoming),
+            Scope::from_discord_api_name("WebHook.Incoming")
+        );
+        assert!(Scope::from_discord_api_name("").is_none());
+        assert!(Scope::from_discord_api_name("identify ").is_none());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Scope::ActivitiesRead), "activities.read".parse());
+        assert_eq!(Ok(Scope::GuildsMembersRead), "guilds.members.read".parse());
+        assert_eq!(Ok(Scope::GuildsMembersRead), "Guilds.Members.Read".parse());
+
+        for scope in Scope::all() {
+            assert_eq!(Ok(*scope), scope.name().parse());
+        }
+
+        let error = "guilds.members".parse::<Scope>().unwrap_err();
+        assert_eq!(
+            ScopeParseError::Unknown {
+                name: "guilds.members".to_owned()
+            },
+            error
+        );
+        assert_eq!("`guilds.members` is not a known scope", error.to_string());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            vec![Scope::Identify, Scope::Guilds],
+            super::parse("IDENTIFY GUILDS")
+        );
+        assert_eq!(
+            vec![Scope::Identify, Scope::Email],
+            super::parse(" identify  unknown.scope Email ")
+        );
+        assert!(super::parse("").is_empty());
+        assert_eq!("identify", Scope::Identify.to_string());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(super::normalize(&[]).is_empty());
+        assert_eq!(
+            vec![Scope::Guilds, Scope::Identify],
+            super::normalize(&[Scope::Guilds, Scope::Identify, Scope::Identify])
+        );
+        assert_eq!(
+            vec![Scope::Bot, Scope::Guilds, Scope::GuildsJoin],
+            super::normalize(&[Scope::GuildsJoin, Scope::Bot, Scope::Guilds, Scope::Bot])
+        );
+    }
+
+    #[test]
+    fn test_requires_authorization_code_grant() {
+        assert!(Scope::ActivitiesRead.requires_authorization_code_grant());
+        assert!(Scope::ActivitiesWrite.requires_authorization_code_grant());
+        assert!(Scope::WebhookIncoming.requires_authorization_code_grant());
+        assert!(!Scope::GuildsMembersRead.requires_authorization_code_grant());
+    }
+
+    #[test]
+    fn test_common_scopes() {
+        assert_eq!([Scope::Bot], Scope::BOT_INVITE_MINIMUM);
+        assert_eq!([Scope::Identify, Scope::Email], Scope::EMAIL_REQUIRED);
+        assert_eq!([Scope::Identify], Scope::IDENTIFY_REQUIRED);
+        assert_eq!("identify email", super::join(Scope::EMAIL_REQUIRED));
+    }
+
+    #[test]
+    fn test_is_sensitive() {
+        let sensitive = Scope::all()
+            .iter()
+            .filter(|scope| scope.is_sensitive())
+            .map(Scope::name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            [
+                "activities.read",
+                "connections",
+                "email",
+                "guilds",
+                "guilds.members.read",
+                "identify",
+                "messages.read",
+                "relationships.read",
+                "rpc.notifications.read",
+            ],
+            sensitive.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let scopes: &'static [Scope] = &[
+            Scope::ApplicationsBuildsRead,
+            Scope::ApplicationsBuildsUpload,
+            Scope::ApplicationsEntitlements,
+            Scope::ApplicationsStoreUpdate,
+            Scope::GdmJoin,
+            Scope::GuildsMembersRead,
+            Scope::RelationshipsRead,
+            Scope::RoleConnectionsWrite,
+            Scope::Voice,
+        ];
+
+        for scope in scopes {
+            serde_test::assert_tokens(
+                scope,
+                &[Token::UnitVariant {
+                    name: "Scope",
+                    variant: scope.name(),
+                }],
+            );
+        }
     }
 
     #[test]
@@ -186,12 +865,16 @@ mod tests {
         assert_eq!("gdm.join", Scope::GdmJoin.name());
         assert_eq!("guilds", Scope::Guilds.name());
         assert_eq!("guilds.join", Scope::GuildsJoin.name());
+        assert_eq!("guilds.members.read", Scope::GuildsMembersRead.name());
+        assert_eq!("guilds.members.read", Scope::GuildsMembersRead.to_string());
         assert_eq!("identify", Scope::Identify.name());
         assert_eq!("messages.read", Scope::MessagesRead.name());
         assert_eq!("relationships.read", Scope::RelationshipsRead.name());
+        assert_eq!("role_connections.write", Scope::RoleConnectionsWrite.name());
         assert_eq!("rpc", Scope::Rpc.name());
         assert_eq!("rpc.api", Scope::RpcApi.name());
         assert_eq!("rpc.notifications.read", Scope::RpcNotificationsRead.name());
+        assert_eq!("voice", Scope::Voice.name());
         assert_eq!("webhook.incoming", Scope::WebhookIncoming.name());
     }
 }
@@ -0,0 +1,466 @@
+//! Correlation of authorization redirects with the requests that started them.
+//!
+//! Between sending a user to the authorization URL and receiving the
+//! redirect, a server has to remember the state it sent, the redirect URI it
+//! used, and the PKCE code verifier if any. An [`AuthorizationSession`]
+//! bundles these, and a [`SessionStore`] keeps sessions keyed by their state
+//! until the redirect arrives.
+//!
+//! Sessions are taken out of the store when the redirect is handled, so a
+//! state can only be used once, and sessions older than the store's time to
+//! live are treated as absent.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use twilight_oauth2::{
+//!     id::ApplicationId,
+//!     session_store::{InMemorySessionStore, SessionStore},
+//!     Client, Scope,
+//! };
+//!
+//! let client = Client::new(ApplicationId(1), "secret", &["https://example.com/cb"])?;
+//! let store = InMemorySessionStore::new();
+//!
+//! let builder = client
+//!     .authorization_url("https://example.com/cb")
+//!     .expect("redirect uri is configured")
+//!     .with_scopes([Scope::Identify])
+//!     .with_state("random state");
+//! let session = builder.session().expect("state is set");
+//! store.insert(session)?;
+//! println!("authorize at: {}", builder.build());
+//!
+//! // After the user is redirected back with the state and a code:
+//! let session = store.take("random state")?.expect("session is pending");
+//! let request = client
+//!     .session_exchange("code", &session)
+//!     .expect("redirect uri is configured")
+//!     .build();
+//! println!("exchange url: {}", request.url());
+//!
+//! // The state can't be used again.
+//! assert!(store.take("random state")?.is_none());
+//! # Ok(()) }
+//! ```
+//!
+//! [`AuthorizationSession`]: struct.AuthorizationSession.html
+//! [`SessionStore`]: trait.SessionStore.html
+
+use super::{Clock, SystemClock};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+/// Pending authorization, from building the authorization URL until the
+/// redirect is handled.
+///
+/// The code verifier is redacted from the `Debug` output.
+#[derive(Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorizationSession {
+    /// PKCE code verifier whose challenge was sent in the authorization URL.
+    ///
+    /// This is set if the challenge was set via
+    /// [`AuthorizationUrlBuilder::code_challenge`] with the plain method, and
+    /// otherwise has to be set by hand.
+    ///
+    /// [`AuthorizationUrlBuilder::code_challenge`]: ../authorization_url/struct.AuthorizationUrlBuilder.html#method.code_challenge
+    pub code_verifier: Option<String>,
+    /// Point in time at which the session was created.
+    pub created_at: SystemTime,
+    /// Redirect URI used in the authorization URL.
+    ///
+    /// The same redirect URI must be used when exchanging the code.
+    pub redirect_uri: String,
+    /// State sent in the authorization URL.
+    pub state: String,
+}
+
+impl AuthorizationSession {
+    /// Create a new session created now, without a code verifier.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn new(state: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+        Self::new_with(state, redirect_uri, &SystemClock)
+    }
+
+    /// Create a new session created at the clock's current time, without a
+    /// code verifier.
+    pub fn new_with(
+        state: impl Into<String>,
+        redirect_uri: impl Into<String>,
+        clock: &impl Clock,
+    ) -> Self {
+        Self {
+            code_verifier: None,
+            created_at: clock.now(),
+            redirect_uri: redirect_uri.into(),
+            state: state.into(),
+        }
+    }
+
+    /// Set the PKCE code verifier whose challenge was sent in the
+    /// authorization URL.
+    pub fn code_verifier(&mut self, code_verifier: impl Into<String>) -> &mut Self {
+        self.code_verifier.replace(code_verifier.into());
+
+        self
+    }
+
+    /// Set the PKCE code verifier whose challenge was sent in the
+    /// authorization URL.
+    ///
+    /// This is the by-value form of [`code_verifier`], for building in one
+    /// expression.
+    ///
+    /// [`code_verifier`]: #method.code_verifier
+    #[must_use = "the session is returned with the value set"]
+    pub fn with_code_verifier(mut self, code_verifier: impl Into<String>) -> Self {
+        self.code_verifier(code_verifier);
+
+        self
+    }
+
+    /// Whether the session is older than a time to live at a point in time.
+    ///
+    /// Sessions created after the point in time aren't expired.
+    pub fn is_expired_at(&self, ttl: Duration, now: SystemTime) -> bool {
+        now.duration_since(self.created_at)
+            .is_ok_and(|age| age >= ttl)
+    }
+}
+
+impl Debug for AuthorizationSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("AuthorizationSession")
+            .field(
+                "code_verifier",
+                &self
+                    .code_verifier
+                    .as_ref()
+                    .map(|_| format_args!("<redacted>")),
+            )
+            .field("created_at", &self.created_at)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Storage of pending authorization sessions keyed by their state.
+pub trait SessionStore {
+    /// Error returned when the store fails.
+    type Error;
+
+    /// Insert a session, replacing any session with the same state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store failed to save the session.
+    fn insert(&self, session: AuthorizationSession) -> Result<(), Self::Error>;
+
+    /// Remove and return the session with a state.
+    ///
+    /// Returns `None` if no session has the state, if it has already been
+    /// taken, or if it has expired. A session can only be taken once, so a
+    /// redirect can't be replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store failed to load the session.
+    fn take(&self, state: &str) -> Result<Option<AuthorizationSession>, Self::Error>;
+}
+
+/// Session store keeping sessions in memory.
+///
+/// Expired sessions are removed whenever a session is inserted, so the store
+/// doesn't grow with abandoned authorizations.
+///
+/// Sessions are lost when the store is dropped, and aren't shared between
+/// processes.
+#[derive(Debug)]
+pub struct InMemorySessionStore<C = SystemClock> {
+    clock: C,
+    sessions: Mutex<HashMap<String, AuthorizationSession>>,
+    ttl: Duration,
+}
+
+impl InMemorySessionStore {
+    /// Default time to live of sessions.
+    ///
+    /// This is ten minutes, which is as long as Discord's authorization codes
+    /// are valid for.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+    /// Create a new, empty, in-memory session store using the
+    /// [`SystemClock`] and the [default time to live].
+    ///
+    /// [`SystemClock`]: ../struct.SystemClock.html
+    /// [default time to live]: #associatedconstant.DEFAULT_TTL
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn new() -> Self {
+        Self::new_with(SystemClock)
+    }
+}
+
+impl<C: Clock> InMemorySessionStore<C> {
+    /// Create a new, empty, in-memory session store using a clock and the
+    /// [default time to live].
+    ///
+    /// [default time to live]: #associatedconstant.DEFAULT_TTL
+    pub fn new_with(clock: C) -> Self {
+        Self {
+            clock,
+            sessions: Mutex::default(),
+            ttl: InMemorySessionStore::DEFAULT_TTL,
+        }
+    }
+
+    /// Set the clock used to determine the age of sessions.
+    ///
+    /// This is useful for testing expiry.
+    pub fn with_clock<D: Clock>(self, clock: D) -> InMemorySessionStore<D> {
+        InMemorySessionStore {
+            clock,
+            sessions: self.sessions,
+            ttl: self.ttl,
+        }
+    }
+
+    /// Set the time to live of sessions.
+    pub fn ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.ttl = ttl;
+
+        self
+    }
+
+    /// Set the time to live of sessions.
+    ///
+    /// This is the by-value form of [`ttl`], for building in one expression.
+    ///
+    /// [`ttl`]: #method.ttl
+    #[must_use = "the store is returned with the value set"]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl(ttl);
+
+        self
+    }
+
+    /// Return the number of sessions in the store, including expired ones
+    /// that haven't been removed yet.
+    pub fn len(&self) -> usize {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether the store contains no sessions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock> SessionStore for InMemorySessionStore<C> {
+    type Error = Infallible;
+
+    fn insert(&self, session: AuthorizationSession) -> Result<(), Self::Error> {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+
+        sessions.retain(|_, session| !session.is_expired_at(self.ttl, now));
+        sessions.insert(session.state.clone(), session);
+
+        Ok(())
+    }
+
+    fn take(&self, state: &str) -> Result<Option<AuthorizationSession>, Self::Error> {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Ok(sessions
+            .remove(state)
+            .filter(|session| !session.is_expired_at(self.ttl, now)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorizationSession, InMemorySessionStore, SessionStore};
+    use crate::{fixtures::ManualClock, id::ApplicationId, request::OAuthRequest, Client};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::Debug,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    assert_fields!(AuthorizationSession: code_verifier, created_at, redirect_uri, state);
+    assert_impl_all!(AuthorizationSession: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(
+        InMemorySessionStore: Debug,
+        Default,
+        SessionStore,
+        Send,
+        Sync
+    );
+    assert_impl_all!(InMemorySessionStore<ManualClock>: SessionStore, Send, Sync);
+
+    fn store(clock: &ManualClock) -> InMemorySessionStore<&ManualClock> {
+        InMemorySessionStore::new_with(clock).with_ttl(Duration::from_secs(30))
+    }
+
+    #[test]
+    fn test_take_once() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        let store = store(&clock);
+        let session = AuthorizationSession::new_with("a", "https://example.com", &clock)
+            .with_code_verifier("verifier");
+
+        store.insert(session.clone()).unwrap();
+        assert_eq!(1, store.len());
+        assert!(store.take("b").unwrap().is_none());
+        assert_eq!(Some(session), store.take("a").unwrap());
+
+        // Replaying the state finds nothing.
+        assert!(store.take("a").unwrap().is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_expiry() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        let store = store(&clock);
+        store
+            .insert(AuthorizationSession::new_with(
+                "a",
+                "https://example.com",
+                &clock,
+            ))
+            .unwrap();
+        store
+            .insert(AuthorizationSession::new_with(
+                "b",
+                "https://example.com",
+                &clock,
+            ))
+            .unwrap();
+
+        clock.advance(Duration::from_secs(29));
+        assert!(store.take("a").unwrap().is_some());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(store.take("b").unwrap().is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_sweep() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        let store = store(&clock);
+        store
+            .insert(AuthorizationSession::new_with(
+                "a",
+                "https://example.com",
+                &clock,
+            ))
+            .unwrap();
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(1, store.len());
+
+        store
+            .insert(AuthorizationSession::new_with(
+                "b",
+                "https://example.com",
+                &clock,
+            ))
+            .unwrap();
+        assert_eq!(1, store.len());
+        assert!(store.take("b").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let session =
+            AuthorizationSession::new("a", "https://example.com").with_code_verifier("verifier");
+        let debug = format!("{session:?}");
+
+        assert!(debug.contains("code_verifier: Some(<redacted>)"));
+        assert!(!debug.contains("verifier\""));
+    }
+
+    #[test]
+    fn test_session_exchange() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/cb"]).unwrap();
+        let session = AuthorizationSession::new("state", "https://example.com/cb")
+            .with_code_verifier("verifier");
+        let request = client.session_exchange("code", &session).unwrap().build();
+
+        assert_eq!(Some("verifier"), request.body.code_verifier.as_deref());
+        assert_eq!("https://example.com/cb", request.body.redirect_uri);
+        assert_eq!(
+            client
+                .access_token_exchange("code", "https://example.com/cb")
+                .unwrap()
+                .with_code_verifier("verifier")
+                .build()
+                .body(),
+            request.body()
+        );
+
+        let unknown = AuthorizationSession::new("state", "https://other.com");
+        assert!(client.session_exchange("code", &unknown).is_err());
+    }
+
+    #[cfg(feature = "loopback")]
+    #[test]
+    fn test_end_to_end() {
+        use crate::{
+            authorization_url::CodeChallengeMethod, loopback::AuthorizationCallback, Scope,
+        };
+
+        let clock = ManualClock::new(UNIX_EPOCH);
+        let store = store(&clock);
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com/cb"]).unwrap();
+
+        let builder = client
+            .authorization_url("https://example.com/cb")
+            .unwrap()
+            .with_scopes([Scope::Identify])
+            .with_state("xyz")
+            .with_code_challenge("verifier", CodeChallengeMethod::Plain);
+        assert!(builder.build().contains("&code_challenge=verifier&"));
+        let session = builder.session_with(&clock).unwrap();
+        store.insert(session.clone()).unwrap();
+
+        let callback = AuthorizationCallback::from_query("code=abc&state=xyz").unwrap();
+        let state = callback.state.as_deref().unwrap();
+        let pending = store.take(state).unwrap().unwrap();
+        assert_eq!(session.redirect_uri, pending.redirect_uri);
+
+        let request = client
+            .session_exchange(&callback.code, &pending)
+            .unwrap()
+            .build();
+        assert_eq!(
+            "client_id=1&client_secret=a&code=abc&code_verifier=verifier\
+            &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&scope=",
+            request.body().unwrap()
+        );
+
+        // A replayed redirect has no session to exchange with.
+        assert!(store.take(state).unwrap().is_none());
+    }
+}
@@ -0,0 +1,440 @@
+#[cfg(all(
+    feature = "model",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+use super::request::webhook_token_exchange::WebhookTokenExchangeResponse;
+use super::{
+    request::refresh_token_exchange::RefreshTokenExchangeRequest, AccessToken, Client, Clock,
+    RefreshToken, TokenResponse, TokenType,
+};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use super::{
+    request::{
+        access_token_exchange::AccessTokenExchangeResponse,
+        client_credentials_grant::ClientCredentialsGrantResponse,
+        refresh_token_exchange::RefreshTokenExchangeResponse,
+    },
+    SystemClock,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Creating a refresh request from a [`StoredToken`] failed.
+///
+/// [`StoredToken`]: struct.StoredToken.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RefreshRequestError {
+    /// The client has no configured redirect URIs, one of which is required
+    /// by refresh token exchanges.
+    RedirectUriMissing,
+    /// The token has no refresh token, such as when it was created via a
+    /// client credentials grant.
+    RefreshTokenMissing,
+}
+
+impl Display for RefreshRequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::RedirectUriMissing => f.write_str("the client has no redirect uris"),
+            Self::RefreshTokenMissing => f.write_str("the token has no refresh token"),
+        }
+    }
+}
+
+impl Error for RefreshRequestError {}
+
+/// Token in a format suitable for persisting across restarts.
+///
+/// Contrasted from the token exchange responses, the expiry is recorded as
+/// an absolute point in time rather than relative to when the response was
+/// received.
+///
+/// The serialized field names are stable and safe to persist:
+///
+/// | Field           | Type                |
+/// | --------------- | ------------------- |
+/// | `access_token`  | string              |
+/// | `expires_at`    | integer             |
+/// | `refresh_token` | string or null      |
+/// | `scope`         | string              |
+/// | `token_type`    | string (`"Bearer"`) |
+///
+/// Stored tokens can be created from any token exchange response. The expiry
+/// is then calculated from the current time, so the conversion should happen
+/// as soon as the response is received.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+/// use twilight_oauth2::{request::access_token_exchange::AccessTokenExchangeResponse, StoredToken};
+///
+/// let response = serde_json::from_str::<AccessTokenExchangeResponse>(r#"{
+///     "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+///     "token_type": "Bearer",
+///     "expires_in": 604800,
+///     "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+///     "scope": "identify"
+/// }"#)?;
+///
+/// let token = StoredToken::from(response);
+/// assert!(token.remaining() <= Duration::from_secs(604_800));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct StoredToken {
+    /// Access token to be used when making requests to the API on the user's
+    /// behalf.
+    pub access_token: AccessToken,
+    /// Unix timestamp in seconds of when the access token expires.
+    pub expires_at: u64,
+    /// Refresh token to use to exchange for another access token and refresh
+    /// token pair.
+    ///
+    /// This is not present for tokens created via a client credentials grant.
+    pub refresh_token: Option<RefreshToken>,
+    /// Space-delimited list of scopes that the token has had approved.
+    pub scope: String,
+    /// Type of token.
+    pub token_type: TokenType,
+}
+
+impl StoredToken {
+    /// Create a new stored token.
+    ///
+    /// The token type is [`TokenType::Bearer`].
+    ///
+    /// [`TokenType::Bearer`]: enum.TokenType.html#variant.Bearer
+    pub fn new(
+        access_token: impl Into<String>,
+        expires_at: u64,
+        refresh_token: Option<String>,
+        scope: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_token: AccessToken::new(access_token),
+            expires_at,
+            refresh_token: refresh_token.map(RefreshToken::from),
+            scope: scope.into(),
+            token_type: TokenType::Bearer,
+        }
+    }
+
+    /// Create a stored token from a response, calculating the expiry from the
+    /// time of a clock.
+    ///
+    /// This is equivalent to the `From` implementations, which use the
+    /// [`SystemClock`].
+    ///
+    /// [`SystemClock`]: struct.SystemClock.html
+    pub fn from_response(response: &impl TokenResponse, clock: &impl Clock) -> Self {
+        Self {
+            access_token: response.access_token().clone(),
+            expires_at: expires_at(response.expires_in(), clock),
+            refresh_token: response.refresh_token().cloned(),
+            scope: response.scope().to_owned(),
+            token_type: response.token_type(),
+        }
+    }
+
+    /// Create a request to exchange the refresh token for another access token
+    /// and refresh token pair.
+    ///
+    /// This is equivalent to building a request via
+    /// [`Client::refresh_token_exchange`] with the token's refresh token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RefreshRequestError::RedirectUriMissing`] if the client has
+    /// no configured redirect URIs.
+    ///
+    /// Returns [`RefreshRequestError::RefreshTokenMissing`] if the token has
+    /// no refresh token.
+    ///
+    /// [`Client::refresh_token_exchange`]: client/struct.Client.html#method.refresh_token_exchange
+    /// [`RefreshRequestError::RedirectUriMissing`]: enum.RefreshRequestError.html#variant.RedirectUriMissing
+    /// [`RefreshRequestError::RefreshTokenMissing`]: enum.RefreshRequestError.html#variant.RefreshTokenMissing
+    pub fn refresh_request<'a>(
+        &'a self,
+        client: &'a Client,
+    ) -> Result<RefreshTokenExchangeRequest<'a>, RefreshRequestError> {
+        let refresh_token = self
+            .refresh_token
+            .as_ref()
+            .ok_or(RefreshRequestError::RefreshTokenMissing)?;
+
+        if client.redirect_uris().is_empty() {
+            return Err(RefreshRequestError::RedirectUriMissing);
+        }
+
+        Ok(client
+            .refresh_token_exchange(refresh_token.expose())
+            .build())
+    }
+
+    /// Return the amount of time until the access token expires.
+    ///
+    /// This is zero if the access token has already expired.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn remaining(&self) -> Duration {
+        self.remaining_with(&SystemClock)
+    }
+
+    /// Return the amount of time until the access token expires according
+    /// to a clock.
+    ///
+    /// This is zero if the access token has already expired.
+    pub fn remaining_with(&self, clock: &impl Clock) -> Duration {
+        self.remaining_at(clock.now())
+    }
+
+    /// Return the amount of time from a point in time until the access token
+    /// expires.
+    ///
+    /// This is zero if the access token has expired by then.
+    pub fn remaining_at(&self, now: SystemTime) -> Duration {
+        let expires_at = UNIX_EPOCH + Duration::from_secs(self.expires_at);
+
+        expires_at.duration_since(now).unwrap_or_default()
+    }
+}
+
+/// Calculate the unix timestamp of when a token issued at the clock's current
+/// time expires.
+fn expires_at(expires_in: u64, clock: &impl Clock) -> u64 {
+    let now = clock
+        .now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    now.saturating_add(expires_in)
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl From<AccessTokenExchangeResponse> for StoredToken {
+    fn from(response: AccessTokenExchangeResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: expires_at(response.expires_in, &SystemClock),
+            refresh_token: Some(response.refresh_token),
+            scope: response.scope,
+            token_type: response.token_type,
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl From<ClientCredentialsGrantResponse> for StoredToken {
+    fn from(response: ClientCredentialsGrantResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: expires_at(response.expires_in, &SystemClock),
+            refresh_token: None,
+            scope: response.scope,
+            token_type: response.token_type,
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl From<RefreshTokenExchangeResponse> for StoredToken {
+    fn from(response: RefreshTokenExchangeResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: expires_at(response.expires_in, &SystemClock),
+            refresh_token: Some(response.refresh_token),
+            scope: response.scope,
+            token_type: response.token_type,
+        }
+    }
+}
+
+#[cfg(feature = "model")]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl From<WebhookTokenExchangeResponse> for StoredToken {
+    fn from(response: WebhookTokenExchangeResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: expires_at(response.expires_in, &SystemClock),
+            refresh_token: Some(response.refresh_token),
+            scope: response.scope,
+            token_type: response.token_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RefreshRequestError, StoredToken};
+    use crate::id::ApplicationId;
+    use crate::{
+        fixtures::{self, ManualClock},
+        Client, RefreshToken, TokenType,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_test::Token;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    assert_impl_all!(RefreshRequestError: Clone, Debug, Display, Eq, Error, PartialEq, Send, Sync);
+    assert_fields!(StoredToken: access_token, expires_at, refresh_token, scope, token_type);
+    assert_impl_all!(
+        StoredToken: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_serde() {
+        let token = StoredToken::new("a", 1_600_000_000, Some("b".to_owned()), "identify");
+
+        serde_test::assert_tokens(
+            &token,
+            &[
+                Token::Struct {
+                    name: "StoredToken",
+                    len: 5,
+                },
+                Token::Str("access_token"),
+                Token::Str("a"),
+                Token::Str("expires_at"),
+                Token::U64(1_600_000_000),
+                Token::Str("refresh_token"),
+                Token::Some,
+                Token::Str("b"),
+                Token::Str("scope"),
+                Token::Str("identify"),
+                Token::Str("token_type"),
+                Token::UnitVariant {
+                    name: "TokenType",
+                    variant: "Bearer",
+                },
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let token = StoredToken::new("a", 1, None, "identify");
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(
+            r#"{"access_token":"a","expires_at":1,"refresh_token":null,"scope":"identify","token_type":"Bearer"}"#,
+            json
+        );
+
+        let loaded = serde_json::from_str::<StoredToken>(&json).unwrap();
+        assert_eq!(token, loaded);
+        assert_eq!(Duration::from_secs(0), loaded.remaining());
+    }
+
+    #[test]
+    fn test_refresh_request() {
+        let client = Client::new(ApplicationId(1), "a", &["https://example.com"]).unwrap();
+        let token = StoredToken::new("access", 1_000, Some("refresh".to_owned()), "identify");
+
+        let expected = client.refresh_token_exchange("refresh").build();
+        assert_eq!(expected, token.refresh_request(&client).unwrap());
+
+        let token = StoredToken::new("access", 1_000, None, "identify");
+        assert_eq!(
+            Err(RefreshRequestError::RefreshTokenMissing),
+            token.refresh_request(&client)
+        );
+    }
+
+    #[test]
+    fn test_refresh_request_redirect_uri_missing() {
+        let client = Client::new(ApplicationId(1), "a", &[]).unwrap();
+        let token = StoredToken::new("access", 1_000, Some("refresh".to_owned()), "identify");
+
+        assert_eq!(
+            Err(RefreshRequestError::RedirectUriMissing),
+            token.refresh_request(&client)
+        );
+    }
+
+    #[test]
+    fn test_remaining() {
+        let token = StoredToken::new("a", 1_000, None, "identify");
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(701));
+        assert_eq!(Duration::from_secs(299), token.remaining_with(&clock));
+
+        clock.advance(Duration::from_secs(298));
+        assert_eq!(Duration::from_secs(1), token.remaining_with(&clock));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Duration::from_secs(0), token.remaining_with(&clock));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Duration::from_secs(0), token.remaining_with(&clock));
+    }
+
+    #[test]
+    fn test_from_response() {
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000));
+        let token = StoredToken::from_response(&fixtures::access_token_response(), &clock);
+        assert_eq!(605_800, token.expires_at);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Duration::from_secs(604_799), token.remaining_with(&clock));
+
+        let token =
+            StoredToken::from_response(&fixtures::client_credentials_grant_response(), &clock);
+        assert!(token.refresh_token.is_none());
+        assert_eq!(605_801, token.expires_at);
+    }
+
+    #[test]
+    fn test_from_responses() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let token = StoredToken::from(fixtures::access_token_response());
+        assert_eq!(
+            "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            token.access_token.expose()
+        );
+        assert!(token.expires_at >= now + 604_800);
+        assert_eq!(
+            Some("D43f5y0ahjqew82jZ4NViEr2YafMKhue"),
+            token.refresh_token.as_ref().map(RefreshToken::expose)
+        );
+        assert_eq!("identify", token.scope);
+        assert_eq!(TokenType::Bearer, token.token_type);
+
+        let token = StoredToken::from(fixtures::client_credentials_grant_response());
+        assert!(token.refresh_token.is_none());
+        assert_eq!("identify connections", token.scope);
+
+        let token = StoredToken::from(fixtures::refresh_token_response());
+        assert_eq!(
+            Some("x7eP5sUjcX9hRRSKW4mMZ6pJxvr1u9"),
+            token.refresh_token.as_ref().map(RefreshToken::expose)
+        );
+
+        #[cfg(feature = "model")]
+        {
+            let token = StoredToken::from(fixtures::webhook_token_response());
+            assert_eq!("webhook.incoming", token.scope);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use super::TokenResponse;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// Access token used to perform requests on behalf of a user.
+///
+/// The token is redacted from the `Debug` output, and `Display` is
+/// intentionally not implemented, so that it doesn't leak into logs. Use
+/// [`expose`] to access the token.
+///
+/// This (de)serializes as the plain token string.
+///
+/// With the `zeroize` feature enabled, the token is zeroed from memory when
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::AccessToken;
+///
+/// let token = AccessToken::new("6qrZcUqja7812RVdnEKjpzOL4CvHBFG");
+///
+/// assert_eq!("AccessToken(<redacted>)", format!("{token:?}"));
+/// assert_eq!("6qrZcUqja7812RVdnEKjpzOL4CvHBFG", token.expose());
+/// ```
+///
+/// [`expose`]: #method.expose
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct AccessToken(String);
+
+impl AccessToken {
+    /// Create a new access token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Create an access token from the access token of a response.
+    ///
+    /// This works with the response of any grant.
+    pub fn from_response<T: TokenResponse>(response: &T) -> Self {
+        response.access_token().clone()
+    }
+
+    /// Return an immutable reference to the token.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume the access token, returning the token.
+    pub fn into_inner(mut self) -> String {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Debug for AccessToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("AccessToken(<redacted>)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for AccessToken {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl From<String> for AccessToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+/// Refresh token used to exchange for another access token and refresh
+/// token pair.
+///
+/// The token is redacted from the `Debug` output, and `Display` is
+/// intentionally not implemented, so that it doesn't leak into logs. Use
+/// [`expose`] to access the token.
+///
+/// This (de)serializes as the plain token string.
+///
+/// With the `zeroize` feature enabled, the token is zeroed from memory when
+/// dropped.
+///
+/// [`expose`]: #method.expose
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct RefreshToken(String);
+
+impl RefreshToken {
+    /// Create a new refresh token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Create a refresh token from the refresh token of a response.
+    ///
+    /// Returns `None` if the response's grant doesn't issue refresh tokens,
+    /// such as the client credentials grant.
+    pub fn from_response<T: TokenResponse>(response: &T) -> Option<Self> {
+        response.refresh_token().cloned()
+    }
+
+    /// Return an immutable reference to the token.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume the refresh token, returning the token.
+    pub fn into_inner(mut self) -> String {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Debug for RefreshToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("RefreshToken(<redacted>)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for RefreshToken {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl From<String> for RefreshToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessToken, RefreshToken};
+    use crate::fixtures;
+    use serde::{Deserialize, Serialize};
+    use serde_test::Token;
+    use static_assertions::{assert_impl_all, assert_not_impl_any};
+    use std::fmt::{Debug, Display};
+
+    assert_impl_all!(
+        AccessToken: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        From<String>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+    assert_not_impl_any!(AccessToken: Display);
+    assert_impl_all!(
+        RefreshToken: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        From<String>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+    assert_not_impl_any!(RefreshToken: Display);
+
+    #[test]
+    fn test_access_token() {
+        let token = AccessToken::new("secret");
+        assert_eq!("secret", token.expose());
+        assert!(!format!("{token:?}").contains("secret"));
+        serde_test::assert_tokens(&token, &[Token::Str("secret")]);
+        assert_eq!("secret", token.into_inner());
+    }
+
+    #[test]
+    fn test_from_response() {
+        let response = fixtures::access_token_response();
+        assert_eq!(
+            "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            AccessToken::from_response(&response).expose()
+        );
+        assert_eq!(
+            Some("D43f5y0ahjqew82jZ4NViEr2YafMKhue"),
+            RefreshToken::from_response(&response)
+                .as_ref()
+                .map(RefreshToken::expose)
+        );
+
+        let response = fixtures::client_credentials_grant_response();
+        assert_eq!(
+            "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            AccessToken::from_response(&response).expose()
+        );
+        assert!(RefreshToken::from_response(&response).is_none());
+
+        let response = fixtures::refresh_token_response();
+        assert_eq!(
+            "Pb2fJ1XM8lA5Ow3fU0JMT2Wr6BLgPl",
+            AccessToken::from_response(&response).expose()
+        );
+
+        #[cfg(feature = "model")]
+        {
+            let response = fixtures::webhook_token_response();
+            assert_eq!(
+                Some("PvPL7ELyMDc1836457XCDh1Y8jPbRm"),
+                RefreshToken::from_response(&response)
+                    .as_ref()
+                    .map(RefreshToken::expose)
+            );
+        }
+    }
+
+    #[test]
+    fn test_refresh_token() {
+        let token = RefreshToken::new("secret");
+        assert_eq!("secret", token.expose());
+        assert!(!format!("{token:?}").contains("secret"));
+        serde_test::assert_tokens(&token, &[Token::Str("secret")]);
+        assert_eq!("secret", token.into_inner());
+    }
+}
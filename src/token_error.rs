@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of error returned by Discord for a token request.
+///
+/// Refer to [RFC 6749 § 5.2] for what each kind means.
+///
+/// [RFC 6749 § 5.2]: https://tools.ietf.org/html/rfc6749#section-5.2
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(rename_all = "snake_case")]
+pub enum TokenErrorKind {
+    /// Client authentication failed, such as due to an invalid client secret.
+    InvalidClient,
+    /// The authorization code or refresh token is invalid, expired, or was
+    /// issued to another client.
+    InvalidGrant,
+    /// The request is missing a parameter or is otherwise malformed.
+    InvalidRequest,
+    /// The requested scope is invalid or exceeds what was granted.
+    InvalidScope,
+    /// The client isn't authorized to use the grant type.
+    UnauthorizedClient,
+    /// The grant type isn't supported.
+    UnsupportedGrantType,
+    /// An error kind not known to this library.
+    #[serde(other)]
+    Unknown,
+}
+
+impl TokenErrorKind {
+    /// Return the name of the error kind.
+    ///
+    /// This is equivalent to what you would get when serializing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_oauth2::TokenErrorKind;
+    ///
+    /// assert_eq!("invalid_grant", TokenErrorKind::InvalidGrant.name());
+    /// ```
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::InvalidClient => "invalid_client",
+            Self::InvalidGrant => "invalid_grant",
+            Self::InvalidRequest => "invalid_request",
+            Self::InvalidScope => "invalid_scope",
+            Self::UnauthorizedClient => "unauthorized_client",
+            Self::UnsupportedGrantType => "unsupported_grant_type",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Error body returned by Discord when a token request fails.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TokenError {
+    /// Kind of error.
+    pub error: TokenErrorKind,
+    /// Human readable description of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TokenError, TokenErrorKind};
+    use serde::{Deserialize, Serialize};
+    use serde_test::Token;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_fields!(TokenError: error, error_description);
+    assert_impl_all!(
+        TokenError: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+    assert_impl_all!(
+        TokenErrorKind: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn test_token_error() {
+        let value = TokenError {
+            error: TokenErrorKind::InvalidGrant,
+            error_description: Some("Invalid \"code\" in request.".to_owned()),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "TokenError",
+                    len: 2,
+                },
+                Token::Str("error"),
+                Token::UnitVariant {
+                    name: "TokenErrorKind",
+                    variant: "invalid_grant",
+                },
+                Token::Str("error_description"),
+                Token::Some,
+                Token::Str("Invalid \"code\" in request."),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_unknown_kind() {
+        serde_test::assert_de_tokens(
+            &TokenErrorKind::Unknown,
+            &[Token::UnitVariant {
+                name: "TokenErrorKind",
+                variant: "temporarily_unavailable",
+            }],
+        );
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use super::SystemClock;
+use super::{AccessToken, RefreshPolicy, RefreshToken, TokenType};
+use std::time::Duration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::SystemTime;
+
+/// Common fields of the responses to every token request.
+///
+/// This allows working with the token in a response without knowing which
+/// grant it came from.
+///
+/// # Examples
+///
+/// Wrap the access token of any response:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_oauth2::{request::access_token_exchange::AccessTokenExchangeResponse, AccessToken};
+///
+/// let response = serde_json::from_str::<AccessTokenExchangeResponse>(r#"{
+///     "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+///     "token_type": "Bearer",
+///     "expires_in": 604800,
+///     "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+///     "scope": "identify"
+/// }"#)?;
+///
+/// let token = AccessToken::from_response(&response);
+/// assert_eq!("6qrZcUqja7812RVdnEKjpzOL4CvHBFG", token.expose());
+/// # Ok(()) }
+/// ```
+pub trait TokenResponse {
+    /// Return an immutable reference to the access token.
+    fn access_token(&self) -> &AccessToken;
+
+    /// Return the number of seconds from issuing that the access token is
+    /// valid.
+    fn expires_in(&self) -> u64;
+
+    /// Return an immutable reference to the refresh token, if the grant
+    /// issues one.
+    fn refresh_token(&self) -> Option<&RefreshToken>;
+
+    /// Return the space-delimited list of scopes that the token has had
+    /// approved.
+    fn scope(&self) -> &str;
+
+    /// Return the type of token.
+    fn token_type(&self) -> TokenType;
+
+    /// Return the amount of time from issuing after which the token should
+    /// be refreshed, according to the default [`RefreshPolicy`].
+    ///
+    /// [`RefreshPolicy`]: struct.RefreshPolicy.html
+    fn refresh_in(&self) -> Duration
+    where
+        Self: Sized,
+    {
+        RefreshPolicy::new().refresh_in(self)
+    }
+
+    /// Return the point in time at which the token should be refreshed,
+    /// according to the default [`RefreshPolicy`].
+    ///
+    /// This assumes the response was just received, so call it as soon as
+    /// the response is parsed.
+    ///
+    /// [`RefreshPolicy`]: struct.RefreshPolicy.html
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn refresh_at(&self) -> SystemTime
+    where
+        Self: Sized,
+    {
+        RefreshPolicy::new().refresh_at(self, &SystemClock)
+    }
+}
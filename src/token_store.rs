@@ -0,0 +1,429 @@
+//! Persistence of tokens across restarts.
+//!
+//! A [`TokenStore`] saves and loads [`StoredToken`]s keyed by an
+//! application-defined key, such as the ID of the user the token belongs to.
+//!
+//! [`InMemoryTokenStore`] is provided for tests, and [`JsonFileTokenStore`]
+//! is provided with the `fs` feature enabled.
+//!
+//! [`InMemoryTokenStore`]: struct.InMemoryTokenStore.html
+//! [`JsonFileTokenStore`]: struct.JsonFileTokenStore.html
+//! [`StoredToken`]: ../struct.StoredToken.html
+//! [`TokenStore`]: trait.TokenStore.html
+
+use super::StoredToken;
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Mutex, PoisonError},
+};
+
+#[cfg(feature = "fs")]
+pub use self::json_file::{JsonFileTokenStore, JsonFileTokenStoreError};
+
+/// Storage of tokens keyed by an application-defined key.
+pub trait TokenStore {
+    /// Error returned when the store fails.
+    type Error;
+
+    /// Load the token stored under a key.
+    ///
+    /// Returns `None` if no token is stored under the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store failed to load the token.
+    fn load(&self, key: &str) -> Result<Option<StoredToken>, Self::Error>;
+
+    /// Save a token under a key, replacing any token already stored under it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store failed to save the token.
+    fn save(&self, key: &str, token: &StoredToken) -> Result<(), Self::Error>;
+
+    /// Delete the token stored under a key.
+    ///
+    /// Deleting a key with no stored token is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store failed to delete the token.
+    fn delete(&self, key: &str) -> Result<(), Self::Error>;
+}
+
+/// Token store keeping tokens in memory.
+///
+/// Tokens are lost when the store is dropped, so this is mostly useful for
+/// tests.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_oauth2::{
+///     token_store::{InMemoryTokenStore, TokenStore},
+///     StoredToken,
+/// };
+///
+/// let store = InMemoryTokenStore::new();
+/// let token = StoredToken::new("access", 1_600_000_000, Some("refresh".to_owned()), "identify");
+///
+/// store.save("user 1", &token).unwrap();
+/// assert_eq!(Some(token), store.load("user 1").unwrap());
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStore {
+    tokens: Mutex<HashMap<String, StoredToken>>,
+}
+
+impl InMemoryTokenStore {
+    /// Create a new, empty, in-memory token store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for InMemoryTokenStore {
+    type Error = Infallible;
+
+    fn load(&self, key: &str) -> Result<Option<StoredToken>, Self::Error> {
+        let tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Ok(tokens.get(key).cloned())
+    }
+
+    fn save(&self, key: &str, token: &StoredToken) -> Result<(), Self::Error> {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        tokens.insert(key.to_owned(), token.clone());
+
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        tokens.remove(key);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fs")]
+mod json_file {
+    use super::{StoredToken, TokenStore};
+    use std::{
+        collections::HashMap,
+        error::Error,
+        fmt::{Display, Formatter, Result as FmtResult},
+        fs::{self, File, OpenOptions},
+        io::{Error as IoError, ErrorKind, Write},
+        path::{Path, PathBuf},
+        process,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex, PoisonError,
+        },
+    };
+
+    /// Number of temporary files created by this process, to give each a
+    /// unique name.
+    static TEMP_FILE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+    /// Using a [`JsonFileTokenStore`] failed.
+    ///
+    /// [`JsonFileTokenStore`]: struct.JsonFileTokenStore.html
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum JsonFileTokenStoreError {
+        /// The file exists but doesn't contain a valid list of tokens.
+        Corrupted {
+            /// Reason for the error.
+            source: serde_json::Error,
+        },
+        /// Reading or writing the file failed.
+        Io {
+            /// Reason for the error.
+            source: IoError,
+        },
+        /// Serializing the list of tokens failed.
+        Serializing {
+            /// Reason for the error.
+            source: serde_json::Error,
+        },
+    }
+
+    impl Display for JsonFileTokenStoreError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            match self {
+                Self::Corrupted { .. } => f.write_str("token store file is corrupted"),
+                Self::Io { .. } => f.write_str("reading or writing the token store file failed"),
+                Self::Serializing { .. } => f.write_str("serializing the stored tokens failed"),
+            }
+        }
+    }
+
+    impl Error for JsonFileTokenStoreError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::Corrupted { source } | Self::Serializing { source } => Some(source),
+                Self::Io { source } => Some(source),
+            }
+        }
+    }
+
+    /// Token store keeping tokens in a JSON file.
+    ///
+    /// All tokens are kept in a single file as a JSON object keyed by the
+    /// token's key. The file is written atomically by writing to a uniquely
+    /// named temporary file next to it, syncing it to disk, and renaming it
+    /// over the original, so a crash while saving never leaves a partially
+    /// written file behind.
+    ///
+    /// On Unix, the file is created readable and writable only by its owner,
+    /// since it contains refresh tokens.
+    ///
+    /// Operations through the same store are serialized. Multiple stores
+    /// must not be used with the same file at the same time.
+    ///
+    /// This is only available with the `fs` feature enabled.
+    #[derive(Debug)]
+    pub struct JsonFileTokenStore {
+        lock: Mutex<()>,
+        path: PathBuf,
+    }
+
+    impl JsonFileTokenStore {
+        /// Create a new token store using the file at the provided path.
+        ///
+        /// The file doesn't need to exist yet; it is created when the first
+        /// token is saved.
+        pub fn new(path: impl Into<PathBuf>) -> Self {
+            Self {
+                lock: Mutex::new(()),
+                path: path.into(),
+            }
+        }
+
+        /// Return an immutable reference to the path of the file.
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        fn read(&self) -> Result<HashMap<String, StoredToken>, JsonFileTokenStoreError> {
+            let bytes = match fs::read(&self.path) {
+                Ok(bytes) => bytes,
+                Err(source) if source.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+                Err(source) => return Err(JsonFileTokenStoreError::Io { source }),
+            };
+
+            serde_json::from_slice(&bytes)
+                .map_err(|source| JsonFileTokenStoreError::Corrupted { source })
+        }
+
+        fn write(
+            &self,
+            tokens: &HashMap<String, StoredToken>,
+        ) -> Result<(), JsonFileTokenStoreError> {
+            let bytes = serde_json::to_vec(tokens)
+                .map_err(|source| JsonFileTokenStoreError::Serializing { source })?;
+
+            let mut temp_path = self.path.clone().into_os_string();
+            temp_path.push(format!(
+                ".{}.{}.tmp",
+                process::id(),
+                TEMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            let result = create_private(temp_path.as_ref())
+                .and_then(|mut file| {
+                    file.write_all(&bytes)?;
+
+                    file.sync_all()
+                })
+                .and_then(|()| fs::rename(&temp_path, &self.path));
+
+            if result.is_err() {
+                let _ = fs::remove_file(&temp_path);
+            }
+
+            result.map_err(|source| JsonFileTokenStoreError::Io { source })
+        }
+    }
+
+    /// Create a new file only readable and writable by its owner.
+    ///
+    /// Permissions are only restricted on Unix.
+    fn create_private(path: &Path) -> Result<File, IoError> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options.open(path)
+    }
+
+    impl TokenStore for JsonFileTokenStore {
+        type Error = JsonFileTokenStoreError;
+
+        fn load(&self, key: &str) -> Result<Option<StoredToken>, Self::Error> {
+            let _lock = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+            Ok(self.read()?.remove(key))
+        }
+
+        fn save(&self, key: &str, token: &StoredToken) -> Result<(), Self::Error> {
+            let _lock = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+            let mut tokens = self.read()?;
+            tokens.insert(key.to_owned(), token.clone());
+
+            self.write(&tokens)
+        }
+
+        fn delete(&self, key: &str) -> Result<(), Self::Error> {
+            let _lock = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+            let mut tokens = self.read()?;
+
+            if tokens.remove(key).is_some() {
+                self.write(&tokens)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{JsonFileTokenStore, JsonFileTokenStoreError, StoredToken, TokenStore};
+        use static_assertions::{assert_fields, assert_impl_all};
+        use std::{env, error::Error, fmt::Debug, fs, path::PathBuf, sync::Arc, thread};
+
+        assert_fields!(JsonFileTokenStoreError::Corrupted: source);
+        assert_fields!(JsonFileTokenStoreError::Io: source);
+        assert_fields!(JsonFileTokenStoreError::Serializing: source);
+        assert_impl_all!(JsonFileTokenStoreError: Debug, Error, Send, Sync);
+        assert_impl_all!(JsonFileTokenStore: Debug, Send, Sync);
+
+        fn path(name: &str) -> PathBuf {
+            let path = env::temp_dir().join(format!(
+                "twilight-oauth2-{}-{}.json",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_file(&path);
+
+            path
+        }
+
+        fn token(access_token: &str) -> StoredToken {
+            StoredToken::new(
+                access_token,
+                1_600_000_000,
+                Some("refresh".to_owned()),
+                "identify",
+            )
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let path = path("round-trip");
+            let store = JsonFileTokenStore::new(&path);
+            assert!(store.load("a").unwrap().is_none());
+
+            store.save("a", &token("1")).unwrap();
+            store.save("b", &token("2")).unwrap();
+            assert_eq!(Some(token("1")), store.load("a").unwrap());
+
+            let store = JsonFileTokenStore::new(&path);
+            assert_eq!(Some(token("2")), store.load("b").unwrap());
+
+            store.delete("a").unwrap();
+            store.delete("c").unwrap();
+            assert!(store.load("a").unwrap().is_none());
+            assert_eq!(Some(token("2")), store.load("b").unwrap());
+
+            fs::remove_file(path).unwrap();
+        }
+
+        #[test]
+        fn test_concurrent_saves() {
+            let path = path("concurrent");
+            let store = Arc::new(JsonFileTokenStore::new(&path));
+
+            let handles = (0..8)
+                .map(|idx| {
+                    let store = Arc::clone(&store);
+
+                    thread::spawn(move || store.save(&idx.to_string(), &token(&idx.to_string())))
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                handle.join().unwrap().unwrap();
+            }
+
+            for idx in 0..8 {
+                let key = idx.to_string();
+                assert_eq!(Some(token(&key)), store.load(&key).unwrap());
+            }
+
+            fs::remove_file(path).unwrap();
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn test_private() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let path = path("private");
+            let store = JsonFileTokenStore::new(&path);
+            store.save("a", &token("1")).unwrap();
+
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(0o600, mode & 0o777);
+
+            fs::remove_file(path).unwrap();
+        }
+
+        #[test]
+        fn test_corrupted() {
+            let path = path("corrupted");
+            fs::write(&path, b"{\"a\": ").unwrap();
+            let store = JsonFileTokenStore::new(&path);
+
+            assert!(matches!(
+                store.load("a"),
+                Err(JsonFileTokenStoreError::Corrupted { .. })
+            ));
+            assert!(matches!(
+                store.save("a", &token("1")),
+                Err(JsonFileTokenStoreError::Corrupted { .. })
+            ));
+
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InMemoryTokenStore, StoredToken, TokenStore};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+
+    assert_impl_all!(InMemoryTokenStore: Debug, Default, Send, Sync);
+
+    #[test]
+    fn test_in_memory() {
+        let store = InMemoryTokenStore::new();
+        let token = StoredToken::new("a", 1_600_000_000, None, "identify");
+        assert!(store.load("user").unwrap().is_none());
+
+        store.save("user", &token).unwrap();
+        assert_eq!(Some(token), store.load("user").unwrap());
+
+        store.delete("user").unwrap();
+        assert!(store.load("user").unwrap().is_none());
+    }
+}